@@ -4,11 +4,10 @@ extern crate core;
 
 use crate::types::FileFormat;
 use clap::Parser;
-use fluentbase_types::SysFuncIdx::SYS_STATE;
-use fluentbase_types::{create_sovereign_import_linker, STATE_DEPLOY, STATE_MAIN};
-use log::debug;
-use rwasm::engine::bytecode::Instruction;
-use rwasm::engine::{RwasmConfig, StateRouterConfig};
+use fluentbase_core::wasm::validator::validate_wasm_module;
+use fluentbase_types::{create_state_router_config, create_system_import_linker};
+use log::{debug, error, info};
+use rwasm::engine::RwasmConfig;
 use rwasm::rwasm::{BinaryFormat, BinaryFormatWriter, RwasmModule};
 use std::{fs, path::Path};
 
@@ -68,6 +67,14 @@ struct Args {
 
     #[arg(long, default_value_t = false)]
     retranslate_main: bool,
+
+    /// Check that the module passes the same validation as the WASM deployment
+    #[arg(long, default_value_t = false)]
+    validate: bool,
+
+    /// Run validation and exit without translation
+    #[arg(long, default_value_t = false)]
+    validate_only: bool,
 }
 
 fn main() {
@@ -92,18 +99,23 @@ fn main() {
         }
     }
 
+    if args.validate || args.validate_only {
+        if let Err(err) = validate_wasm_module(&wasm_binary) {
+            error!("module validation failed ({}): {}", err.exit_code, err.reason);
+            std::process::exit(1);
+        }
+        info!("module is valid");
+        if args.validate_only {
+            return;
+        }
+    }
+
     let mut config = RwasmModule::default_config(None);
     config
         .rwasm_config(RwasmConfig {
-            state_router: Some(StateRouterConfig {
-                states: Box::new([
-                    ("deploy".to_string(), STATE_DEPLOY),
-                    ("main".to_string(), STATE_MAIN),
-                ]),
-                opcode: Instruction::Call(SYS_STATE.into()),
-            }),
+            state_router: Some(create_state_router_config()),
             entrypoint_name: None,
-            import_linker: Some(create_sovereign_import_linker()),
+            import_linker: Some(create_system_import_linker()),
             wrap_import_functions: true,
         })
         .consume_fuel(false);
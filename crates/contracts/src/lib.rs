@@ -5,13 +5,21 @@ use fluentbase_types::{address, Address};
 pub const ECL_CONTRACT_ADDRESS: Address = address!("5200000000000000000000000000000000000001");
 pub const WCL_CONTRACT_ADDRESS: Address = address!("5200000000000000000000000000000000000002");
 
-// precompiles
-pub const PRECOMPILE_BLAKE2_ADDRESS: Address = address!("0000000000000000000000000000000000000001");
-pub const PRECOMPILE_BN128_ADDRESS: Address = address!("0000000000000000000000000000000000000002");
-pub const PRECOMPILE_IDENTITY_ADDRESS: Address =
+// precompiles (standard Ethereum addresses, secp256k1 is ecrecover)
+pub const PRECOMPILE_SECP256K1_ADDRESS: Address =
+    address!("0000000000000000000000000000000000000001");
+pub const PRECOMPILE_SHA256_ADDRESS: Address = address!("0000000000000000000000000000000000000002");
+pub const PRECOMPILE_RIPEMD160_ADDRESS: Address =
     address!("0000000000000000000000000000000000000003");
-pub const PRECOMPILE_KZG_POINT_EVALUATION_ADDRESS: Address =
+pub const PRECOMPILE_IDENTITY_ADDRESS: Address =
     address!("0000000000000000000000000000000000000004");
 pub const PRECOMPILE_MODEXP_ADDRESS: Address = address!("0000000000000000000000000000000000000005");
-pub const PRECOMPILE_SECP256K1_ADDRESS: Address =
+pub const PRECOMPILE_BN128_ADD_ADDRESS: Address =
     address!("0000000000000000000000000000000000000006");
+pub const PRECOMPILE_BN128_MUL_ADDRESS: Address =
+    address!("0000000000000000000000000000000000000007");
+pub const PRECOMPILE_BN128_PAIR_ADDRESS: Address =
+    address!("0000000000000000000000000000000000000008");
+pub const PRECOMPILE_BLAKE2_ADDRESS: Address = address!("0000000000000000000000000000000000000009");
+pub const PRECOMPILE_KZG_POINT_EVALUATION_ADDRESS: Address =
+    address!("000000000000000000000000000000000000000a");
@@ -1,15 +1,42 @@
 use fluentbase_types::{address, Address};
+use revm_primitives::RWASM_MAX_CODE_SIZE;
 
 pub const ECL_CONTRACT_ADDRESS: Address = address!("5200000000000000000000000000000000000001");
 pub const WCL_CONTRACT_ADDRESS: Address = address!("5200000000000000000000000000000000000002");
+/// System account that keeps registered VM loaders in its storage (see `VmLoaderEntry`)
+pub const VM_REGISTRY_ADDRESS: Address = address!("5200000000000000000000000000000000000003");
 
-// precompiles
-pub const PRECOMPILE_BLAKE2_ADDRESS: Address = address!("0000000000000000000000000000000000000001");
-pub const PRECOMPILE_BN128_ADDRESS: Address = address!("0000000000000000000000000000000000000002");
-pub const PRECOMPILE_IDENTITY_ADDRESS: Address =
+// precompiles (standard Ethereum addresses, secp256k1 is ecrecover)
+pub const PRECOMPILE_SECP256K1_ADDRESS: Address =
+    address!("0000000000000000000000000000000000000001");
+pub const PRECOMPILE_SHA256_ADDRESS: Address = address!("0000000000000000000000000000000000000002");
+pub const PRECOMPILE_RIPEMD160_ADDRESS: Address =
     address!("0000000000000000000000000000000000000003");
-pub const PRECOMPILE_KZG_POINT_EVALUATION_ADDRESS: Address =
+pub const PRECOMPILE_IDENTITY_ADDRESS: Address =
     address!("0000000000000000000000000000000000000004");
 pub const PRECOMPILE_MODEXP_ADDRESS: Address = address!("0000000000000000000000000000000000000005");
-pub const PRECOMPILE_SECP256K1_ADDRESS: Address =
+pub const PRECOMPILE_BN128_ADD_ADDRESS: Address =
     address!("0000000000000000000000000000000000000006");
+pub const PRECOMPILE_BN128_MUL_ADDRESS: Address =
+    address!("0000000000000000000000000000000000000007");
+pub const PRECOMPILE_BN128_PAIR_ADDRESS: Address =
+    address!("0000000000000000000000000000000000000008");
+pub const PRECOMPILE_BLAKE2_ADDRESS: Address = address!("0000000000000000000000000000000000000009");
+pub const PRECOMPILE_KZG_POINT_EVALUATION_ADDRESS: Address =
+    address!("000000000000000000000000000000000000000a");
+
+// WASM deployment gas
+/// Max size of the WASM deploy payload (like EIP-3860 it's twice bigger than max code size)
+pub const WASM_MAX_INITCODE_SIZE: usize = 2 * RWASM_MAX_CODE_SIZE;
+/// Cost of WASM module validation per each 32-byte word of the WASM module
+pub const WASM_VALIDATION_WORD_COST: u64 = 8;
+/// Cost of WASM to rWASM translation per each 32-byte word of the WASM module
+pub const WASM_TRANSLATION_WORD_COST: u64 = 16;
+/// Cost of storing each byte of WASM source code (same as EVM code deposit)
+pub const WASM_CODE_DEPOSIT_BYTE_COST: u64 = 200;
+/// Cost of storing each byte of rWASM code, it's cheaper since it can be derived from the source
+pub const RWASM_CODE_DEPOSIT_BYTE_COST: u64 = 50;
+/// Max number of 64KiB pages WASM module can declare for its memory
+pub const WASM_MAX_MEMORY_PAGES: u64 = 1024;
+/// Max number of elements WASM module can declare for its table
+pub const WASM_MAX_TABLE_SIZE: u32 = 4096;
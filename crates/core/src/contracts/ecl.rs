@@ -27,11 +27,19 @@ pub fn main() {
     match method_id {
         EVM_CREATE_METHOD_ID => {
             let method_input = input_helper.decode_method_input::<EvmCreateMethodInput>();
+            if method_input.depth == 0 {
+                am.warm_up_transaction(&cr);
+                am.write_parent_block_hash(&cr);
+            }
             let method_output = _evm_create(&cr, &am, method_input);
             LowLevelSDK::sys_write(&method_output.encode_to_vec(0));
         }
         EVM_CALL_METHOD_ID => {
             let method_input = input_helper.decode_method_input::<EvmCallMethodInput>();
+            if method_input.depth == 0 {
+                am.warm_up_transaction(&cr);
+                am.write_parent_block_hash(&cr);
+            }
             let method_output = _evm_call(&cr, &am, method_input);
             LowLevelSDK::sys_write(&method_output.encode_to_vec(0));
             debug_log!("ecl(main): return exit_code={}", method_output.exit_code);
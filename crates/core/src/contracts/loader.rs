@@ -10,7 +10,7 @@ use fluentbase_sdk::{
     LowLevelSDK,
     EVM_CALL_METHOD_ID,
 };
-use fluentbase_types::STATE_MAIN;
+use fluentbase_types::{call_entrypoint_state, STATE_MAIN};
 
 pub fn deploy() {}
 
@@ -19,12 +19,17 @@ pub fn main() {
     let mut contract_input_data = ExecutionContext::contract_input_full();
     let am = JzktAccountManager::default();
     let mut gas_limit = contract_input_data.contract_gas_limit as u32;
+    let state = call_entrypoint_state(
+        &contract_input_data.contract_input,
+        &contract_input_data.contract_value,
+    );
     let method_data = EvmCallMethodInput {
         callee: contract_input_data.contract_address,
         value: contract_input_data.contract_value,
         input: contract_input_data.contract_input,
         gas_limit: gas_limit as u64,
         depth: 0,
+        state,
     };
     let core_input = CoreInput::new(EVM_CALL_METHOD_ID, method_data);
     contract_input_data.contract_input = core_input.encode_to_vec(0).into();
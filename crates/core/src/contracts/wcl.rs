@@ -11,6 +11,8 @@ use fluentbase_sdk::{
     LowLevelSDK,
     WasmCallMethodInput,
     WasmCreateMethodInput,
+    EVM_CALL_METHOD_ID,
+    EVM_CREATE_METHOD_ID,
     WASM_CALL_METHOD_ID,
     WASM_CREATE_METHOD_ID,
 };
@@ -22,14 +24,21 @@ pub fn main() {
     let am = JzktAccountManager::default();
     let input_helper = InputHelper::new(cr);
     let method_id = input_helper.decode_method_id();
+    // VM registry calls loaders with EVM method ids, WASM method inputs have the same layout
     match method_id {
-        WASM_CREATE_METHOD_ID => {
+        WASM_CREATE_METHOD_ID | EVM_CREATE_METHOD_ID => {
             let method_input = input_helper.decode_method_input::<WasmCreateMethodInput>();
+            if method_input.depth == 0 {
+                am.write_parent_block_hash(&cr);
+            }
             let method_output = _wasm_create(&cr, &am, method_input);
             LowLevelSDK::sys_write(&method_output.encode_to_vec(0));
         }
-        WASM_CALL_METHOD_ID => {
+        WASM_CALL_METHOD_ID | EVM_CALL_METHOD_ID => {
             let method_input = input_helper.decode_method_input::<WasmCallMethodInput>();
+            if method_input.depth == 0 {
+                am.write_parent_block_hash(&cr);
+            }
             let method_output = _wasm_call(&cr, &am, method_input);
             LowLevelSDK::sys_write(&method_output.encode_to_vec(0));
             debug_log!(
@@ -43,25 +52,26 @@ pub fn main() {
 
 #[cfg(test)]
 mod tests {
-    use fluentbase_codec::Encoder;
+    use fluentbase_codec::{BufferDecoder, Encoder};
     use fluentbase_sdk::{
         ContractInput,
         CoreInput,
         LowLevelSDK,
         WasmCreateMethodInput,
+        WasmCreateMethodOutput,
         WASM_CREATE_METHOD_ID,
     };
+    use fluentbase_types::ExitCode;
     use revm_primitives::U256;
 
-    #[test]
-    fn test_greeting_deploy() {
+    fn deploy_greeting(gas_limit: u64) -> WasmCreateMethodOutput {
         let wasm_bytecode = include_bytes!("../../../../examples/bin/greeting.wasm");
         let core_input = CoreInput {
             method_id: WASM_CREATE_METHOD_ID,
             method_data: WasmCreateMethodInput {
                 value: U256::ZERO,
                 bytecode: wasm_bytecode.into(),
-                gas_limit: 3_000_000,
+                gas_limit,
                 salt: None,
                 depth: 0,
             },
@@ -73,6 +83,27 @@ mod tests {
         .encode_to_vec(0);
         LowLevelSDK::with_test_input(contract_input);
         super::main();
-        assert!(LowLevelSDK::get_test_output().len() > 0);
+        let output = LowLevelSDK::get_test_output();
+        assert!(output.len() > 0);
+        let mut method_output = WasmCreateMethodOutput::default();
+        let mut buffer_decoder = BufferDecoder::new(&output);
+        WasmCreateMethodOutput::decode_body(&mut buffer_decoder, 0, &mut method_output);
+        method_output
+    }
+
+    #[test]
+    fn test_greeting_deploy() {
+        let method_output = deploy_greeting(3_000_000);
+        assert_eq!(method_output.exit_code, ExitCode::Ok.into_i32());
+        // deployment is charged, but unused gas is returned
+        assert!(method_output.gas > 0 && method_output.gas < 3_000_000);
+    }
+
+    #[test]
+    fn test_greeting_deploy_out_of_gas() {
+        // there is not enough gas to pay for the code deposit
+        let method_output = deploy_greeting(1_000);
+        assert_eq!(method_output.exit_code, ExitCode::OutOfFuel.into_i32());
+        assert!(method_output.address.is_none());
     }
 }
@@ -10,6 +10,7 @@ pub mod codehash;
 pub mod codesize;
 #[cfg(feature = "ecl")]
 pub mod create;
+pub mod eof;
 pub mod extcodecopy;
 pub mod extcodehash;
 pub mod extcodesize;
@@ -3,7 +3,7 @@ use crate::{
     helpers::{exec_evm_bytecode, exit_code_from_evm_error},
 };
 use fluentbase_sdk::{AccountManager, ContextReader, EvmCallMethodInput, EvmCallMethodOutput};
-use fluentbase_types::ExitCode;
+use fluentbase_types::{BytecodeType, ExitCode};
 use revm_interpreter::{
     analysis::to_analysed,
     primitives::Bytecode,
@@ -30,6 +30,13 @@ pub fn _evm_call<CR: ContextReader, AM: AccountManager>(
     let (mut caller_account, _) = am.account(cr.contract_caller());
     let (mut callee_account, _) = am.account(cr.contract_address());
 
+    // static calls can't transfer value, and calls without value don't change accounts, so we
+    // must not write them (writes are forbidden in static frames)
+    if cr.contract_is_static() && !input.value.is_zero() {
+        return EvmCallMethodOutput::from_exit_code(ExitCode::WriteProtection)
+            .with_gas(input.gas_limit, 0);
+    }
+
     // create new checkpoint position in the journal
     let checkpoint = am.checkpoint();
 
@@ -44,30 +51,31 @@ pub fn _evm_call<CR: ContextReader, AM: AccountManager>(
     }
 
     if caller_account.address != callee_account.address {
-        // do transfer from caller to callee
-        match am.transfer(&mut caller_account, &mut callee_account, input.value) {
-            Ok(_) => {}
-            Err(exit_code) => {
-                return EvmCallMethodOutput::from_exit_code(exit_code).with_gas(input.gas_limit, 0);
+        if !input.value.is_zero() {
+            // do transfer from caller to callee
+            match am.transfer(&mut caller_account, &mut callee_account, input.value) {
+                Ok(_) => {}
+                Err(exit_code) => {
+                    am.rollback(checkpoint);
+                    return EvmCallMethodOutput::from_exit_code(exit_code)
+                        .with_gas(input.gas_limit, 0);
+                }
             }
+            // write current account state before doing nested calls
+            am.write_account(&caller_account);
+            am.write_account(&callee_account);
         }
-        // write current account state before doing nested calls
-        am.write_account(&caller_account);
-        am.write_account(&callee_account);
-    } else {
-        // what if self-transfer amount exceeds our balance?
-        if input.value > caller_account.balance {
-            return EvmCallMethodOutput::from_exit_code(ExitCode::InsufficientBalance)
-                .with_gas(input.gas_limit, 0);
-        }
-        // write only one account's state since caller equals callee
-        am.write_account(&caller_account);
+    } else if input.value > caller_account.balance {
+        // self-transfer doesn't change the balance, but it can't exceed it
+        am.rollback(checkpoint);
+        return EvmCallMethodOutput::from_exit_code(ExitCode::InsufficientBalance)
+            .with_gas(input.gas_limit, 0);
     }
 
     // check is it precompile
     if let Some(result) = am.precompile(&input.callee, &input.input, input.gas_limit) {
         if ExitCode::from(result.exit_code).is_ok() {
-            am.commit();
+            am.checkpoint_merge();
         } else {
             am.rollback(checkpoint);
         }
@@ -87,13 +95,20 @@ pub fn _evm_call<CR: ContextReader, AM: AccountManager>(
             am.preimage(&callee_account.source_code_hash),
         )
     };
+    // EOF can't be deployed, but genesis state can still have it, and it must not be executed
+    // as legacy bytecode
+    if BytecodeType::from_slice(source_bytecode.as_ref()) == BytecodeType::EOF {
+        am.rollback(checkpoint);
+        return EvmCallMethodOutput::from_exit_code(ExitCode::NotSupportedCall)
+            .with_gas(input.gas_limit, 0);
+    }
     // load bytecode and convert it to analysed (we can safely unwrap here)
     let bytecode =
         BytecodeLocked::try_from(to_analysed(Bytecode::new_raw(source_bytecode))).unwrap();
 
-    // if bytecode is empty then commit result and return empty buffer
+    // if bytecode is empty then merge result and return empty buffer
     if bytecode.is_empty() {
-        am.commit();
+        am.checkpoint_merge();
         debug_log!("ecl(_evm_call): empty bytecode exit_code=Ok");
         return EvmCallMethodOutput::from_exit_code(ExitCode::Ok).with_gas(input.gas_limit, 0);
     }
@@ -118,7 +133,7 @@ pub fn _evm_call<CR: ContextReader, AM: AccountManager>(
     );
 
     if matches!(result.result, return_ok!()) {
-        am.commit();
+        am.checkpoint_merge();
     } else {
         am.rollback(checkpoint);
     }
@@ -138,3 +153,128 @@ pub fn _evm_call<CR: ContextReader, AM: AccountManager>(
         gas_refund: result.gas.refunded(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::_evm_call;
+    use crate::evm::create::_evm_create;
+    use alloc::vec::Vec;
+    use fluentbase_sdk::{
+        Account,
+        AccountManager,
+        ContractInput,
+        EvmCallMethodInput,
+        EvmCreateMethodInput,
+        JzktAccountManager,
+        LowLevelSDK,
+    };
+    use fluentbase_types::{Address, Bytes, ExitCode, U256};
+
+    #[test]
+    fn test_static_call_reads_state() {
+        let am = JzktAccountManager::default();
+        let address = Address::with_last_byte(0x31);
+        // SLOAD(0), MSTORE(0), RETURN(0, 32)
+        let bytecode = Bytes::from_static(&[0x5f, 0x54, 0x5f, 0x52, 0x60, 0x20, 0x5f, 0xf3]);
+        Account::new(address).update_bytecode(&am, &bytecode, None, &Bytes::new(), None);
+        am.write_storage(address, U256::ZERO, U256::from(7));
+        // caller is a WASM contract that does static call into EVM contract
+        let cr = ContractInput {
+            contract_address: address,
+            contract_caller: Address::with_last_byte(0x32),
+            contract_is_static: true,
+            ..Default::default()
+        };
+        let input = EvmCallMethodInput {
+            callee: address,
+            gas_limit: 100_000,
+            ..Default::default()
+        };
+        LowLevelSDK::with_test_is_static(true);
+        let output = _evm_call(&cr, &am, input.clone());
+        let value_output = _evm_call(
+            &cr,
+            &am,
+            EvmCallMethodInput {
+                value: U256::from(1),
+                ..input
+            },
+        );
+        LowLevelSDK::with_test_is_static(false);
+        assert_eq!(output.exit_code, ExitCode::Ok.into_i32());
+        assert_eq!(output.output.as_ref(), &U256::from(7).to_be_bytes::<32>());
+        // value can't be transferred in static mode
+        assert_eq!(value_output.exit_code, ExitCode::WriteProtection.into_i32());
+    }
+
+    #[test]
+    fn test_static_call_can_revert() {
+        let am = JzktAccountManager::default();
+        let address = Address::with_last_byte(0x35);
+        // SLOAD(0), REVERT(0, 0)
+        let bytecode = Bytes::from_static(&[0x5f, 0x54, 0x5f, 0x5f, 0xfd]);
+        Account::new(address).update_bytecode(&am, &bytecode, None, &Bytes::new(), None);
+        let cr = ContractInput {
+            contract_address: address,
+            contract_caller: Address::with_last_byte(0x36),
+            contract_is_static: true,
+            ..Default::default()
+        };
+        let input = EvmCallMethodInput {
+            callee: address,
+            gas_limit: 100_000,
+            ..Default::default()
+        };
+        // static frame rolls back only its own warm accesses, so revert isn't a write violation
+        LowLevelSDK::with_test_is_static(true);
+        let output = _evm_call(&cr, &am, input);
+        LowLevelSDK::with_test_is_static(false);
+        assert_eq!(output.exit_code, ExitCode::Panic.into_i32());
+    }
+
+    #[test]
+    fn test_eof_deployment_is_rejected() {
+        let am = JzktAccountManager::default();
+        let address = Address::with_last_byte(0x33);
+        let cr = ContractInput {
+            contract_address: address,
+            contract_caller: Address::with_last_byte(0x34),
+            ..Default::default()
+        };
+        // valid container with PUSH0, PUSH0, RETURN code section
+        let eof_initcode = [
+            0xef, 0x00, 0x01, 0x01, 0x00, 0x04, 0x02, 0x00, 0x01, 0x00, 0x03, 0xff, 0x00, 0x00,
+            0x00, 0x00, 0x80, 0x00, 0x02, 0x5f, 0x5f, 0xf3,
+        ];
+        let create = |bytecode: &[u8]| {
+            let input = EvmCreateMethodInput {
+                bytecode: Bytes::copy_from_slice(bytecode),
+                gas_limit: 100_000,
+                ..Default::default()
+            };
+            _evm_create(&cr, &am, input).exit_code
+        };
+        assert_eq!(create(&eof_initcode), ExitCode::NotSupportedCall.into_i32());
+        assert_eq!(
+            create(&eof_initcode[..eof_initcode.len() - 1]),
+            ExitCode::InvalidEofContainer.into_i32()
+        );
+
+        // legacy CREATE with EOF initcode fails and returns zero address:
+        // MSTORE(0, initcode), MSTORE(0, CREATE(0, 0, len)), RETURN(0, 32)
+        let mut bytecode = Vec::from([0x7f]);
+        bytecode.extend_from_slice(&eof_initcode);
+        bytecode.resize(33, 0x00);
+        bytecode.extend_from_slice(&[0x5f, 0x52, 0x60, eof_initcode.len() as u8, 0x5f, 0x5f]);
+        bytecode.extend_from_slice(&[0xf0, 0x5f, 0x52, 0x60, 0x20, 0x5f, 0xf3]);
+        Account::new(address).update_bytecode(&am, &bytecode.into(), None, &Bytes::new(), None);
+        let input = EvmCallMethodInput {
+            callee: address,
+            gas_limit: 1_000_000,
+            ..Default::default()
+        };
+        let output = _evm_call(&cr, &am, input);
+        assert_eq!(output.exit_code, ExitCode::Ok.into_i32());
+        assert_eq!(output.output.as_ref(), &[0u8; 32]);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     debug_log,
+    evm::eof::EofContainer,
     helpers::{exec_evm_bytecode, exit_code_from_evm_error},
 };
 use fluentbase_sdk::{
@@ -11,7 +12,7 @@ use fluentbase_sdk::{
     LowLevelAPI,
     LowLevelSDK,
 };
-use fluentbase_types::{ExitCode, B256};
+use fluentbase_types::{BytecodeType, ExitCode, B256};
 use revm_interpreter::{
     analysis::to_analysed,
     gas,
@@ -58,6 +59,19 @@ pub fn _evm_create<CR: ContextReader, AM: AccountManager>(
             .with_gas(input.gas_limit, 0);
     }
 
+    // EOF can't be deployed until its execution is supported (see `EofContainer::decode`),
+    // invalid containers are still reported as invalid
+    if BytecodeType::from_slice(input.bytecode.as_ref()) == BytecodeType::EOF {
+        let exit_code = match EofContainer::decode(input.bytecode.clone()) {
+            Ok(_) => ExitCode::NotSupportedCall,
+            Err(err) => {
+                debug_log!("ecl(_evm_create): return: Err: invalid EOF initcode: {:?}", err);
+                ExitCode::InvalidEofContainer
+            }
+        };
+        return EvmCreateMethodOutput::from_exit_code(exit_code).with_gas(input.gas_limit, 0);
+    }
+
     // calc source code hash
     let mut source_code_hash: B256 = B256::ZERO;
     LowLevelSDK::crypto_keccak256(
@@ -111,7 +125,7 @@ pub fn _evm_create<CR: ContextReader, AM: AccountManager>(
             .with_output(result.output)
             .with_gas(result.gas.remaining(), result.gas.refunded());
     }
-    if !result.output.is_empty() && result.output.first() == Some(&0xEF) {
+    if result.output.first() == Some(&0xEF) {
         am.rollback(checkpoint);
         debug_log!("ecl(_evm_create): return: Err: {:?}", result.result);
         return EvmCreateMethodOutput::from_exit_code(ExitCode::CreateContractStartingWithEF)
@@ -145,8 +159,8 @@ pub fn _evm_create<CR: ContextReader, AM: AccountManager>(
         contract_account.address
     );
 
-    // commit all changes made
-    am.commit();
+    // merge all changes made into the parent frame
+    am.checkpoint_merge();
 
     return EvmCreateMethodOutput::from_exit_code(ExitCode::Ok)
         .with_output(result.output)
@@ -0,0 +1,396 @@
+use alloc::{vec, vec::Vec};
+use core::ops::Range;
+use fluentbase_types::{Bytes, EOF_MAGIC, EOF_VERSION};
+
+const KIND_TERMINATOR: u8 = 0x00;
+const KIND_TYPES: u8 = 0x01;
+const KIND_CODE: u8 = 0x02;
+const KIND_CONTAINER: u8 = 0x03;
+const KIND_DATA: u8 = 0xff;
+
+const EOF_MAX_CODE_SECTIONS: usize = 1024;
+const EOF_MAX_CONTAINER_SECTIONS: usize = 256;
+const EOF_MAX_STACK_HEIGHT: u16 = 1023;
+const EOF_NON_RETURNING: u8 = 0x80;
+
+const DATALOAD: u8 = 0xd0;
+const DATALOADN: u8 = 0xd1;
+const DATASIZE: u8 = 0xd2;
+const DATACOPY: u8 = 0xd3;
+const RJUMP: u8 = 0xe0;
+const RJUMPI: u8 = 0xe1;
+const RJUMPV: u8 = 0xe2;
+const CALLF: u8 = 0xe3;
+const RETF: u8 = 0xe4;
+const JUMPF: u8 = 0xe5;
+const DUPN: u8 = 0xe6;
+const SWAPN: u8 = 0xe7;
+const EXCHANGE: u8 = 0xe8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EofError {
+    InvalidMagic,
+    InvalidVersion,
+    InvalidHeader,
+    InvalidTypesSection,
+    InvalidSectionSize,
+    UndefinedOpcode(u8),
+    TruncatedImmediate,
+    InvalidJumpTarget,
+    InvalidCodeSection,
+    InvalidDataOffset,
+    MissingTerminator,
+}
+
+/// Signature of the code section from the types section
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EofTypes {
+    pub inputs: u8,
+    pub outputs: u8,
+    pub max_stack_height: u16,
+}
+
+impl EofTypes {
+    pub fn is_returning(&self) -> bool {
+        self.outputs != EOF_NON_RETURNING
+    }
+}
+
+/// Validated EIP-3540 container, all section ranges are absolute offsets inside `raw`
+#[derive(Debug, Clone)]
+pub struct EofContainer {
+    pub raw: Bytes,
+    pub types: Vec<EofTypes>,
+    pub code_sections: Vec<Range<usize>>,
+    pub container_sections: Vec<Range<usize>>,
+    pub data_section: Range<usize>,
+}
+
+struct HeaderReader<'a> {
+    input: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> HeaderReader<'a> {
+    fn read_u8(&mut self) -> Result<u8, EofError> {
+        let value = *self.input.get(self.offset).ok_or(EofError::InvalidHeader)?;
+        self.offset += 1;
+        Ok(value)
+    }
+
+    fn read_u16(&mut self) -> Result<u16, EofError> {
+        let value = read_u16(self.input, self.offset).map_err(|_| EofError::InvalidHeader)?;
+        self.offset += 2;
+        Ok(value)
+    }
+
+    fn expect_kind(&mut self, kind: u8) -> Result<(), EofError> {
+        if self.read_u8()? != kind {
+            return Err(EofError::InvalidHeader);
+        }
+        Ok(())
+    }
+
+    fn read_section_sizes(&mut self, max_sections: usize) -> Result<Vec<usize>, EofError> {
+        let num_sections = self.read_u16()? as usize;
+        if num_sections == 0 || num_sections > max_sections {
+            return Err(EofError::InvalidHeader);
+        }
+        (0..num_sections)
+            .map(|_| match self.read_u16()? {
+                0 => Err(EofError::InvalidSectionSize),
+                size => Ok(size as usize),
+            })
+            .collect()
+    }
+}
+
+impl EofContainer {
+    /// Parses container header and body and validates all code sections.
+    ///
+    /// EIP-5450 stack validation isn't applied and there are no EXT*CALL, EOFCREATE and
+    /// RETURNCONTRACT opcodes yet, so even valid containers are rejected on deployment.
+    pub fn decode(raw: Bytes) -> Result<Self, EofError> {
+        let input = raw.as_ref();
+        if !input.starts_with(&EOF_MAGIC) {
+            return Err(EofError::InvalidMagic);
+        }
+        if input.get(2) != Some(&EOF_VERSION) {
+            return Err(EofError::InvalidVersion);
+        }
+        let mut reader = HeaderReader { input, offset: 3 };
+        reader.expect_kind(KIND_TYPES)?;
+        let types_size = reader.read_u16()? as usize;
+        reader.expect_kind(KIND_CODE)?;
+        let code_sizes = reader.read_section_sizes(EOF_MAX_CODE_SECTIONS)?;
+        if types_size != code_sizes.len() * 4 {
+            return Err(EofError::InvalidTypesSection);
+        }
+        let container_sizes = if input.get(reader.offset) == Some(&KIND_CONTAINER) {
+            reader.offset += 1;
+            reader.read_section_sizes(EOF_MAX_CONTAINER_SECTIONS)?
+        } else {
+            Vec::new()
+        };
+        reader.expect_kind(KIND_DATA)?;
+        let data_size = reader.read_u16()? as usize;
+        reader.expect_kind(KIND_TERMINATOR)?;
+
+        // body must exactly match sizes declared in the header
+        let body_size = types_size
+            + code_sizes.iter().sum::<usize>()
+            + container_sizes.iter().sum::<usize>()
+            + data_size;
+        if reader.offset + body_size != input.len() {
+            return Err(EofError::InvalidSectionSize);
+        }
+        let mut offset = reader.offset;
+        let mut next_section = |size: usize| {
+            let section = offset..offset + size;
+            offset += size;
+            section
+        };
+        let types = input[next_section(types_size)]
+            .chunks_exact(4)
+            .map(|chunk| EofTypes {
+                inputs: chunk[0],
+                outputs: chunk[1],
+                max_stack_height: u16::from_be_bytes([chunk[2], chunk[3]]),
+            })
+            .collect::<Vec<_>>();
+        let code_sections = code_sizes.into_iter().map(&mut next_section).collect();
+        let container_sections = container_sizes.into_iter().map(&mut next_section).collect();
+        let data_section = next_section(data_size);
+
+        let container = Self {
+            raw,
+            types,
+            code_sections,
+            container_sections,
+            data_section,
+        };
+        container.validate()?;
+        Ok(container)
+    }
+
+    pub fn code_section(&self, index: usize) -> &[u8] {
+        &self.raw[self.code_sections[index].clone()]
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.raw[self.data_section.clone()]
+    }
+
+    fn validate(&self) -> Result<(), EofError> {
+        // the first section is an entrypoint, it takes no inputs and never returns
+        let first = self.types[0];
+        if first.inputs != 0 || first.is_returning() {
+            return Err(EofError::InvalidTypesSection);
+        }
+        for types in self.types.iter() {
+            if types.inputs > 0x7f
+                || (types.outputs > 0x7f && types.is_returning())
+                || types.max_stack_height > EOF_MAX_STACK_HEIGHT
+            {
+                return Err(EofError::InvalidTypesSection);
+            }
+        }
+        for index in 0..self.code_sections.len() {
+            self.validate_code_section(index)?;
+        }
+        for section in self.container_sections.iter() {
+            Self::decode(self.raw.slice(section.clone()))?;
+        }
+        Ok(())
+    }
+
+    fn validate_code_section(&self, index: usize) -> Result<(), EofError> {
+        let code = self.code_section(index);
+        let is_returning = self.types[index].is_returning();
+        let mut is_instruction = vec![false; code.len()];
+        let mut jump_targets = Vec::new();
+        let mut last_opcode = 0x00;
+        let mut pc = 0;
+        while pc < code.len() {
+            let opcode = code[pc];
+            is_instruction[pc] = true;
+            let immediate_size = match opcode {
+                0x60..=0x7f => (opcode - 0x5f) as usize,
+                RJUMP | RJUMPI => {
+                    let relative_offset = read_u16(code, pc + 1)? as i16;
+                    jump_targets.push(pc as isize + 3 + relative_offset as isize);
+                    2
+                }
+                RJUMPV => {
+                    let max_index = *code.get(pc + 1).ok_or(EofError::TruncatedImmediate)?;
+                    let immediate_size = 1 + (max_index as usize + 1) * 2;
+                    let end = (pc + 1 + immediate_size) as isize;
+                    for i in 0..=max_index as usize {
+                        let relative_offset = read_u16(code, pc + 2 + i * 2)? as i16;
+                        jump_targets.push(end + relative_offset as isize);
+                    }
+                    immediate_size
+                }
+                CALLF | JUMPF => {
+                    let target_index = read_u16(code, pc + 1)? as usize;
+                    let target = self
+                        .types
+                        .get(target_index)
+                        .ok_or(EofError::InvalidCodeSection)?;
+                    // CALLF must return back, JUMPF can't make non-returning section returning
+                    if (opcode == CALLF && !target.is_returning())
+                        || (opcode == JUMPF && target.is_returning() && !is_returning)
+                    {
+                        return Err(EofError::InvalidCodeSection);
+                    }
+                    2
+                }
+                RETF if !is_returning => return Err(EofError::InvalidCodeSection),
+                DATALOADN => {
+                    let data_offset = read_u16(code, pc + 1)? as usize;
+                    if data_offset + 32 > self.data_section.len() {
+                        return Err(EofError::InvalidDataOffset);
+                    }
+                    2
+                }
+                DUPN | SWAPN | EXCHANGE => 1,
+                _ if is_eof_opcode(opcode) => 0,
+                _ => return Err(EofError::UndefinedOpcode(opcode)),
+            };
+            if pc + immediate_size >= code.len() && immediate_size > 0 {
+                return Err(EofError::TruncatedImmediate);
+            }
+            last_opcode = opcode;
+            pc += 1 + immediate_size;
+        }
+        // execution can't fall through into the next section
+        if !matches!(
+            last_opcode,
+            0x00 | 0xf3 | 0xfd | 0xfe | RJUMP | RETF | JUMPF
+        ) {
+            return Err(EofError::MissingTerminator);
+        }
+        for target in jump_targets {
+            if target < 0
+                || !is_instruction
+                    .get(target as usize)
+                    .copied()
+                    .unwrap_or(false)
+            {
+                return Err(EofError::InvalidJumpTarget);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Opcodes w/o immediates that are allowed inside EOF code sections, legacy code and gas
+/// introspection, legacy calls and creates are rejected together with undefined opcodes.
+fn is_eof_opcode(opcode: u8) -> bool {
+    matches!(
+        opcode,
+        0x00..=0x0b
+            | 0x10..=0x1d
+            | 0x20
+            | 0x30..=0x37
+            | 0x3a
+            | 0x3d..=0x3e
+            | 0x40..=0x4a
+            | 0x50..=0x55
+            | 0x59
+            | 0x5b..=0x5f
+            | 0x80..=0xa4
+            | DATALOAD
+            | DATASIZE
+            | DATACOPY
+            | RETF
+            | 0xf3
+            | 0xfd
+            | 0xfe
+    )
+}
+
+fn read_u16(input: &[u8], offset: usize) -> Result<u16, EofError> {
+    match input.get(offset..offset + 2) {
+        Some(bytes) => Ok(u16::from_be_bytes([bytes[0], bytes[1]])),
+        None => Err(EofError::TruncatedImmediate),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EofContainer, EofError};
+    use alloc::vec::Vec;
+    use fluentbase_types::Bytes;
+
+    /// Builds container with a single non-returning code section and the data section
+    fn container(code: &[u8], data: &[u8]) -> Bytes {
+        let mut raw = Vec::from([0xef, 0x00, 0x01, 0x01, 0x00, 0x04, 0x02, 0x00, 0x01]);
+        raw.extend_from_slice(&(code.len() as u16).to_be_bytes());
+        raw.push(0xff);
+        raw.extend_from_slice(&(data.len() as u16).to_be_bytes());
+        raw.extend_from_slice(&[0x00, 0x00, 0x80, 0x00, 0x02]);
+        raw.extend_from_slice(code);
+        raw.extend_from_slice(data);
+        raw.into()
+    }
+
+    fn decode(code: &[u8], data: &[u8]) -> Result<(), EofError> {
+        EofContainer::decode(container(code, data)).map(|_| ())
+    }
+
+    #[test]
+    fn test_eof_container_validation() {
+        // PUSH1 0, PUSH1 0, RETURN
+        let container =
+            EofContainer::decode(container(&[0x60, 0x00, 0x5f, 0xf3], &[1, 2])).unwrap();
+        assert_eq!(container.code_section(0), &[0x60, 0x00, 0x5f, 0xf3]);
+        assert_eq!(container.data(), &[1, 2]);
+        assert_eq!(
+            EofContainer::decode(Bytes::from_static(&[0xef, 0x00, 0x02])).unwrap_err(),
+            EofError::InvalidVersion
+        );
+        assert_eq!(
+            EofContainer::decode(Bytes::from_static(&[0xef, 0x00, 0x01, 0x01])).unwrap_err(),
+            EofError::InvalidHeader
+        );
+        // body is longer than declared in the header
+        let mut raw = container(&[0x00], &[]).to_vec();
+        raw.push(0x00);
+        assert_eq!(
+            EofContainer::decode(raw.into()).unwrap_err(),
+            EofError::InvalidSectionSize
+        );
+        // legacy JUMP and unsupported EXTCALL
+        assert_eq!(
+            decode(&[0x56, 0x00], &[]),
+            Err(EofError::UndefinedOpcode(0x56))
+        );
+        assert_eq!(
+            decode(&[0xf8, 0x00], &[]),
+            Err(EofError::UndefinedOpcode(0xf8))
+        );
+        assert_eq!(
+            decode(&[0x61, 0x00], &[]),
+            Err(EofError::TruncatedImmediate)
+        );
+        assert_eq!(decode(&[0x5f], &[]), Err(EofError::MissingTerminator));
+        // RJUMP into PUSH1 immediate and RJUMPI back to the section start
+        assert_eq!(
+            decode(&[0x60, 0x00, 0xe0, 0xff, 0xfc], &[]),
+            Err(EofError::InvalidJumpTarget)
+        );
+        assert_eq!(decode(&[0x5f, 0xe1, 0xff, 0xfc, 0x00], &[]), Ok(()));
+        assert_eq!(
+            decode(&[0xd1, 0x00, 0x01, 0x00], &[0u8; 32]),
+            Err(EofError::InvalidDataOffset)
+        );
+        assert_eq!(decode(&[0xd1, 0x00, 0x00, 0x50, 0x00], &[0u8; 32]), Ok(()));
+        // RETF and CALLF to non-returning section aren't allowed
+        assert_eq!(decode(&[0xe4], &[]), Err(EofError::InvalidCodeSection));
+        assert_eq!(
+            decode(&[0xe3, 0x00, 0x00, 0x00], &[]),
+            Err(EofError::InvalidCodeSection)
+        );
+    }
+}
@@ -165,7 +165,7 @@ impl<'cr, 'am, CR: ContextReader, AM: AccountManager> Host for FluentHost<'cr, '
     #[inline]
     fn selfdestruct(&mut self, address: Address, target: Address) -> Option<SelfDestructResult> {
         let [had_value, target_exists, is_cold, previously_destroyed] =
-            self.am.unwrap().self_destruct(address, target);
+            self.am.unwrap().self_destruct(address, target).ok()?;
         Some(SelfDestructResult {
             had_value,
             target_exists,
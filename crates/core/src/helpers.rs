@@ -1,7 +1,7 @@
 use crate::fluent_host::FluentHost;
 #[cfg(feature = "ecl")]
 use crate::{loader::_loader_call, loader::_loader_create};
-use alloc::{boxed::Box, string::ToString, vec, vec::Vec};
+use alloc::{boxed::Box, vec, vec::Vec};
 use core::{marker::PhantomData, mem::take};
 use fluentbase_codec::Encoder;
 use fluentbase_sdk::{
@@ -15,13 +15,14 @@ use fluentbase_sdk::{
     LowLevelAPI,
 };
 use fluentbase_types::{
+    call_entrypoint_state,
     create_sovereign_import_linker,
+    create_state_router_config,
+    split_prefixed_wasm_initcode,
     Address,
     Bytes,
     ExitCode,
-    SysFuncIdx::SYS_STATE,
-    STATE_DEPLOY,
-    STATE_MAIN,
+    WASM_MAGIC,
 };
 use revm_interpreter::{
     opcode::make_instruction_table,
@@ -37,9 +38,9 @@ use revm_interpreter::{
     InterpreterResult,
     SharedMemory,
 };
-use revm_primitives::{CancunSpec, CreateScheme};
+use revm_primitives::{spec_to_generic, CreateScheme};
 use rwasm::{
-    engine::{bytecode::Instruction, RwasmConfig, StateRouterConfig},
+    engine::RwasmConfig,
     rwasm::{BinaryFormat, BinaryFormatWriter, RwasmModule},
 };
 
@@ -53,17 +54,21 @@ macro_rules! decode_method_input {
     }};
 }
 
+/// Splits WASM deploy payload into the WASM module and constructor arguments. Payload is either a
+/// plain WASM module without arguments or `args_len (u32 LE) | args | module`, where arguments
+/// are length-prefixed, so the module is never parsed to find its end.
+pub fn split_wasm_initcode(payload: &[u8]) -> Result<(&[u8], &[u8]), ExitCode> {
+    if payload.starts_with(&WASM_MAGIC) {
+        return Ok((payload, &[]));
+    }
+    split_prefixed_wasm_initcode(payload).ok_or(ExitCode::CompilationError)
+}
+
 #[inline(always)]
 pub fn wasm2rwasm(wasm_binary: &[u8]) -> Result<Vec<u8>, ExitCode> {
     let mut config = RwasmModule::default_config(None);
     config.rwasm_config(RwasmConfig {
-        state_router: Some(StateRouterConfig {
-            states: Box::new([
-                ("deploy".to_string(), STATE_DEPLOY),
-                ("main".to_string(), STATE_MAIN),
-            ]),
-            opcode: Instruction::Call(SYS_STATE.into()),
-        }),
+        state_router: Some(create_state_router_config()),
         entrypoint_name: None,
         import_linker: Some(create_sovereign_import_linker()),
         wrap_import_functions: true,
@@ -107,6 +112,40 @@ macro_rules! debug_log {
     ($($arg:tt)*) => {{}};
 }
 
+/// Forwards execution context of the current frame to the callee with new gas limit and input
+pub(crate) fn contract_input_from_context<CR: ContextReader>(
+    cr: &CR,
+    gas_limit: u64,
+    input: Bytes,
+) -> ContractInput {
+    ContractInput {
+        journal_checkpoint: cr.journal_checkpoint(),
+        contract_gas_limit: gas_limit,
+        contract_address: cr.contract_address(),
+        contract_caller: cr.contract_caller(),
+        contract_input: input,
+        contract_value: cr.contract_value(),
+        contract_is_static: cr.contract_is_static(),
+        block_chain_id: cr.block_chain_id(),
+        block_coinbase: cr.block_coinbase(),
+        block_timestamp: cr.block_timestamp(),
+        block_number: cr.block_number(),
+        block_difficulty: cr.block_difficulty(),
+        block_gas_limit: cr.block_gas_limit(),
+        block_base_fee: cr.block_base_fee(),
+        block_parent_hash: cr.block_parent_hash(),
+        block_hardforks: cr.block_hardforks(),
+        tx_gas_limit: cr.tx_gas_limit(),
+        tx_nonce: cr.tx_nonce(),
+        tx_gas_price: cr.tx_gas_price(),
+        tx_gas_priority_fee: cr.tx_gas_priority_fee(),
+        tx_caller: cr.tx_caller(),
+        tx_access_list: cr.tx_access_list(),
+        tx_blob_hashes: cr.tx_blob_hashes(),
+        tx_max_fee_per_blob_gas: cr.tx_max_fee_per_blob_gas(),
+    }
+}
+
 fn contract_input_from_call_inputs<CR: ContextReader>(
     cr: &CR,
     call_inputs: &Box<CallInputs>,
@@ -127,6 +166,8 @@ fn contract_input_from_call_inputs<CR: ContextReader>(
         block_difficulty: cr.block_difficulty(),
         block_gas_limit: cr.block_gas_limit(),
         block_base_fee: cr.block_base_fee(),
+        block_parent_hash: cr.block_parent_hash(),
+        block_hardforks: cr.block_hardforks(),
         tx_gas_limit: cr.tx_gas_limit(),
         tx_nonce: cr.tx_nonce(),
         tx_gas_price: cr.tx_gas_price(),
@@ -158,6 +199,8 @@ fn contract_input_from_create_inputs<CR: ContextReader>(
         block_difficulty: cr.block_difficulty(),
         block_gas_limit: cr.block_gas_limit(),
         block_base_fee: cr.block_base_fee(),
+        block_parent_hash: cr.block_parent_hash(),
+        block_hardforks: cr.block_hardforks(),
         tx_gas_limit: cr.tx_gas_limit(),
         tx_nonce: cr.tx_nonce(),
         tx_gas_price: cr.tx_gas_price(),
@@ -213,6 +256,7 @@ fn exec_evm_call<CR: ContextReader, AM: AccountManager>(
     let return_memory_offset = inputs.return_memory_offset.clone();
 
     let contract_input = contract_input_from_call_inputs(cr, &inputs, Bytes::new());
+    let state = call_entrypoint_state(&inputs.input, &inputs.transfer.value);
     let method_data = EvmCallMethodInput {
         callee: inputs.contract,
         // here we take transfer value, because for DELEGATECALL it's not apparent
@@ -220,6 +264,7 @@ fn exec_evm_call<CR: ContextReader, AM: AccountManager>(
         input: take(&mut inputs.input),
         gas_limit: inputs.gas_limit,
         depth,
+        state,
     };
     let call_output = _loader_call(&contract_input, am, method_data);
 
@@ -285,7 +330,11 @@ pub(crate) fn exec_evm_bytecode<CR: ContextReader, AM: AccountManager>(
     }
     let contract_address = contract.address;
 
-    let instruction_table = make_instruction_table::<FluentHost<CR, AM>, CancunSpec>();
+    // spec is taken from the chain's hardfork schedule, so new forks don't require ECL rebuild
+    let instruction_table = spec_to_generic!(
+        cr.block_spec_id(),
+        make_instruction_table::<FluentHost<CR, AM>, SPEC>()
+    );
 
     let mut interpreter = Interpreter::new(Box::new(contract), gas_limit, is_static);
     let mut host = FluentHost::new(cr, am);
@@ -437,3 +486,30 @@ impl<CR: ContextReader> InputHelper<CR> {
         core_input
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::split_wasm_initcode;
+    use fluentbase_types::ExitCode;
+
+    #[test]
+    fn test_split_wasm_initcode() {
+        let wasm_bytecode = include_bytes!("../../../examples/bin/greeting.wasm");
+        // no constructor arguments
+        let (code, args) = split_wasm_initcode(wasm_bytecode).unwrap();
+        assert_eq!(code, wasm_bytecode);
+        assert!(args.is_empty());
+        // ABI encoded arguments are length-prefixed, even if they look like a WASM section
+        let arg = [0u8; 32];
+        let mut payload = (arg.len() as u32).to_le_bytes().to_vec();
+        payload.extend_from_slice(&arg);
+        payload.extend_from_slice(wasm_bytecode);
+        let (code, args) = split_wasm_initcode(&payload).unwrap();
+        assert_eq!(code, wasm_bytecode);
+        assert_eq!(args, arg);
+        // arguments can't exceed the payload and must be followed by WASM module
+        payload[..4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(split_wasm_initcode(&payload).unwrap_err(), ExitCode::CompilationError);
+        assert_eq!(split_wasm_initcode(&arg).unwrap_err(), ExitCode::CompilationError);
+    }
+}
@@ -18,11 +18,15 @@ pub mod helpers;
 pub mod loader;
 #[cfg(any(
     feature = "precompile_blake2",
-    feature = "precompile_bn128",
+    feature = "precompile_bn128_add",
+    feature = "precompile_bn128_mul",
+    feature = "precompile_bn128_pair",
     feature = "precompile_identity",
     feature = "precompile_kzg_point_evaluation",
     feature = "precompile_modexp",
+    feature = "precompile_ripemd160",
     feature = "precompile_secp256k1",
+    feature = "precompile_sha256",
 ))]
 pub mod precompiles;
 pub mod wasm;
@@ -34,11 +38,15 @@ macro_rules! export_and_forward {
             feature = "loader",
             feature = "wcl",
             feature = "precompile_blake2",
-            feature = "precompile_bn128",
+            feature = "precompile_bn128_add",
+            feature = "precompile_bn128_mul",
+            feature = "precompile_bn128_pair",
             feature = "precompile_identity",
             feature = "precompile_kzg_point_evaluation",
             feature = "precompile_modexp",
+            feature = "precompile_ripemd160",
             feature = "precompile_secp256k1",
+            feature = "precompile_sha256",
         ))]
         #[cfg(not(feature = "std"))]
         #[no_mangle]
@@ -52,12 +60,24 @@ macro_rules! export_and_forward {
             contracts::wcl::$fn_name();
             #[cfg(feature = "precompile_blake2")]
             precompiles::blake2::$fn_name();
+            #[cfg(feature = "precompile_bn128_add")]
+            precompiles::bn128_add::$fn_name();
+            #[cfg(feature = "precompile_bn128_mul")]
+            precompiles::bn128_mul::$fn_name();
+            #[cfg(feature = "precompile_bn128_pair")]
+            precompiles::bn128_pair::$fn_name();
             #[cfg(feature = "precompile_identity")]
             precompiles::identity::$fn_name();
+            #[cfg(feature = "precompile_kzg_point_evaluation")]
+            precompiles::kzg_point_evaluation::$fn_name();
             #[cfg(feature = "precompile_modexp")]
             precompiles::modexp::$fn_name();
+            #[cfg(feature = "precompile_ripemd160")]
+            precompiles::ripemd160::$fn_name();
             #[cfg(feature = "precompile_secp256k1")]
             precompiles::secp256k1::$fn_name();
+            #[cfg(feature = "precompile_sha256")]
+            precompiles::sha256::$fn_name();
         }
     };
 }
@@ -1,26 +1,80 @@
 use crate::{
+    consts::VM_REGISTRY_ADDRESS,
     evm::{call::_evm_call, create::_evm_create},
+    helpers::contract_input_from_context,
     wasm::{call::_wasm_call, create::_wasm_create},
 };
+use fluentbase_codec::{BufferDecoder, Encoder};
 use fluentbase_sdk::{
     AccountManager,
     ContextReader,
+    CoreInput,
     EvmCallMethodInput,
     EvmCallMethodOutput,
     EvmCreateMethodInput,
     EvmCreateMethodOutput,
+    EVM_CALL_METHOD_ID,
+    EVM_CREATE_METHOD_ID,
 };
-use fluentbase_types::BytecodeType;
+use fluentbase_types::{
+    split_prefixed_wasm_initcode,
+    Address,
+    BytecodeType,
+    Bytes,
+    ExitCode,
+    VmLoaderEntry,
+    STATE_MAIN,
+    U256,
+    VM_REGISTRY_MAX_ENTRIES,
+};
+
+/// Finds VM loader registered in the state for the bytecode, the longest magic wins
+///
+/// The registry is read from the committed state, so reads don't warm the registry slots and
+/// registry changes take effect starting from the next transaction.
+pub fn resolve_vm_loader<AM: AccountManager>(am: &AM, bytecode: &[u8]) -> Option<VmLoaderEntry> {
+    // accounts without code can't match any magic, so don't read the registry for them
+    if bytecode.is_empty() {
+        return None;
+    }
+    let (count, _) = am.storage(VM_REGISTRY_ADDRESS, U256::ZERO, true);
+    let count = count.as_limbs()[0].min(VM_REGISTRY_MAX_ENTRIES);
+    (1..=count)
+        .filter_map(|slot| {
+            let (value, _) = am.storage(VM_REGISTRY_ADDRESS, U256::from(slot), true);
+            VmLoaderEntry::from_storage_value(value)
+        })
+        .filter(|vm_loader| vm_loader.matches(bytecode))
+        .max_by_key(|vm_loader| vm_loader.magic.len())
+}
 
 pub fn _loader_call<CR: ContextReader, AM: AccountManager>(
     cr: &CR,
     am: &AM,
     input: EvmCallMethodInput,
 ) -> EvmCallMethodOutput {
+    // precompiles are rWASM contracts w/o source code, they're dispatched by the EVM call
+    if am.is_precompile(&input.callee) {
+        return _evm_call(cr, am, input);
+    }
     let (account, _) = am.account(input.callee);
     let source_code = am.preimage(&account.source_code_hash);
+    // VMs registered in the state take precedence over the built-in ones
+    if let Some(vm_loader) = resolve_vm_loader(am, source_code.as_ref()) {
+        let gas_limit = input.gas_limit;
+        return exec_vm_loader(cr, am, vm_loader.address, EVM_CALL_METHOD_ID, input, gas_limit)
+            .and_then(|output| decode_vm_output::<EvmCallMethodOutput>(&output))
+            .and_then(|output| {
+                // loader can't return more gas than it was given
+                if output.gas_remaining > gas_limit {
+                    return Err(ExitCode::FatalExternalError);
+                }
+                Ok(output)
+            })
+            .unwrap_or_else(EvmCallMethodOutput::from_exit_code);
+    }
     match BytecodeType::from_slice(source_code.as_ref()) {
-        BytecodeType::EVM => _evm_call(cr, am, input),
+        BytecodeType::EVM | BytecodeType::EOF => _evm_call(cr, am, input),
         BytecodeType::WASM => _wasm_call(cr, am, input),
     }
 }
@@ -30,8 +84,225 @@ pub fn _loader_create<CR: ContextReader, AM: AccountManager>(
     am: &AM,
     input: EvmCreateMethodInput,
 ) -> EvmCreateMethodOutput {
+    // WASM module can be prefixed with constructor arguments, so it's matched by the module magic
+    let bytecode = split_prefixed_wasm_initcode(input.bytecode.as_ref())
+        .map(|(module, _)| module)
+        .unwrap_or(input.bytecode.as_ref());
+    if let Some(vm_loader) = resolve_vm_loader(am, bytecode) {
+        let gas_limit = input.gas_limit;
+        return exec_vm_loader(cr, am, vm_loader.address, EVM_CREATE_METHOD_ID, input, gas_limit)
+            .and_then(|output| decode_vm_output::<EvmCreateMethodOutput>(&output))
+            .and_then(|output| {
+                // loader can't return more gas than it was given
+                if output.gas > gas_limit {
+                    return Err(ExitCode::FatalExternalError);
+                }
+                Ok(output)
+            })
+            .unwrap_or_else(EvmCreateMethodOutput::from_exit_code);
+    }
     match BytecodeType::from_slice(input.bytecode.as_ref()) {
-        BytecodeType::EVM => _evm_create(cr, am, input),
+        BytecodeType::EVM | BytecodeType::EOF => _evm_create(cr, am, input),
         BytecodeType::WASM => _wasm_create(cr, am, input),
     }
 }
+
+/// Executes system contract of the VM loader with the method input in the current context
+fn exec_vm_loader<CR: ContextReader, AM: AccountManager, I: Encoder<I> + Default>(
+    cr: &CR,
+    am: &AM,
+    address: Address,
+    method_id: u32,
+    method_input: I,
+    gas_limit: u64,
+) -> Result<Bytes, ExitCode> {
+    let (vm_account, _) = am.account(address);
+    let core_input = CoreInput::new(method_id, method_input);
+    // fuel is limited by u32, the VM returns remaining gas in its method output
+    let mut fuel = gas_limit.min(u32::MAX as u64) as u32;
+    let contract_input =
+        contract_input_from_context(cr, fuel as u64, core_input.encode_to_vec(0).into());
+    let (output, exit_code) = am.exec_hash(
+        vm_account.rwasm_code_hash.as_ptr(),
+        &contract_input.encode_to_vec(0),
+        &mut fuel as *mut u32,
+        STATE_MAIN,
+    );
+    if exit_code != ExitCode::Ok.into_i32() {
+        return Err(ExitCode::from(exit_code));
+    }
+    Ok(output)
+}
+
+/// Decodes method output of the VM loader, both call and create outputs start with the `output`
+/// bytes field. Output that doesn't fit into the buffer fails instead of panicking the decoder.
+fn decode_vm_output<O: Encoder<O> + Default>(output: &[u8]) -> Result<O, ExitCode> {
+    if output.len() < O::HEADER_SIZE {
+        return Err(ExitCode::InputDecodeFailure);
+    }
+    let mut buffer_decoder = BufferDecoder::new(output);
+    let (bytes_offset, bytes_length) = buffer_decoder.read_bytes_header(0);
+    match bytes_offset.checked_add(bytes_length) {
+        Some(bytes_end) if bytes_end <= output.len() => {}
+        _ => return Err(ExitCode::InputDecodeFailure),
+    }
+    let mut method_output = O::default();
+    O::decode_body(&mut buffer_decoder, 0, &mut method_output);
+    Ok(method_output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{_loader_call, _loader_create, resolve_vm_loader};
+    use crate::{consts::VM_REGISTRY_ADDRESS, helpers::wasm2rwasm};
+    use alloc::{format, string::String};
+    use fluentbase_codec::Encoder;
+    use fluentbase_sdk::{
+        Account,
+        AccountManager,
+        ContractInput,
+        EvmCallMethodInput,
+        EvmCallMethodOutput,
+        EvmCreateMethodInput,
+        EvmCreateMethodOutput,
+        JzktAccountManager,
+    };
+    use fluentbase_types::{Address, Bytes, ExitCode, VmLoaderEntry, U256, WASM_MAGIC};
+
+    fn register_vm_loaders(am: &JzktAccountManager, vm_loaders: &[VmLoaderEntry]) {
+        am.write_storage(VM_REGISTRY_ADDRESS, U256::ZERO, U256::from(vm_loaders.len()));
+        for (i, vm_loader) in vm_loaders.iter().enumerate() {
+            am.write_storage(
+                VM_REGISTRY_ADDRESS,
+                U256::from(i + 1),
+                vm_loader.to_storage_value(),
+            );
+        }
+        // registry is read from the committed state
+        am.commit();
+    }
+
+    /// Deploys VM loader that writes the same output for any input
+    fn deploy_vm_loader(am: &JzktAccountManager, address: Address, output: &[u8]) {
+        let data: String = output.iter().map(|byte| format!("\\{:02x}", byte)).collect();
+        let wasm_bytecode = wat::parse_str(format!(
+            r#"(module
+                (import "fluentbase_v1alpha" "_sys_write" (func $sys_write (param i32 i32)))
+                (memory 1)
+                (data (i32.const 0) "{}")
+                (func (export "deploy"))
+                (func (export "main") (call $sys_write (i32.const 0) (i32.const {}))))"#,
+            data,
+            output.len()
+        ))
+        .unwrap();
+        let rwasm_bytecode = wasm2rwasm(&wasm_bytecode).unwrap();
+        Account::new(address).update_bytecode(
+            am,
+            &wasm_bytecode.into(),
+            None,
+            &rwasm_bytecode.into(),
+            None,
+        );
+    }
+
+    fn call_contract(
+        am: &JzktAccountManager,
+        callee: Address,
+        gas_limit: u64,
+    ) -> EvmCallMethodOutput {
+        let cr = ContractInput {
+            contract_address: callee,
+            contract_caller: Address::with_last_byte(0x40),
+            ..Default::default()
+        };
+        let input = EvmCallMethodInput {
+            callee,
+            gas_limit,
+            ..Default::default()
+        };
+        _loader_call(&cr, am, input)
+    }
+
+    fn create_contract(am: &JzktAccountManager, bytecode: Bytes) -> EvmCreateMethodOutput {
+        let cr = ContractInput {
+            contract_caller: Address::with_last_byte(0x40),
+            ..Default::default()
+        };
+        let input = EvmCreateMethodInput {
+            bytecode,
+            gas_limit: 3_000_000,
+            ..Default::default()
+        };
+        _loader_create(&cr, am, input)
+    }
+
+    #[test]
+    fn test_wasm_with_constructor_args_is_deployed_by_loader() {
+        let wasm_bytecode = include_bytes!("../../../examples/bin/greeting.wasm");
+        let args = [0xaau8; 32];
+        let mut payload = (args.len() as u32).to_le_bytes().to_vec();
+        payload.extend_from_slice(&args);
+        payload.extend_from_slice(wasm_bytecode);
+        // built-in WASM loader is used if there is no registered one
+        let am = JzktAccountManager::default();
+        let output = create_contract(&am, payload.clone().into());
+        assert_eq!(output.exit_code, ExitCode::Ok.into_i32());
+        assert!(output.address.is_some());
+        // registered WASM loader receives the payload with arguments
+        let am = JzktAccountManager::default();
+        let vm_address = Address::with_last_byte(0x42);
+        let loader_output = EvmCreateMethodOutput::from_exit_code(ExitCode::Ok)
+            .with_address(Address::with_last_byte(0x46))
+            .with_gas(1_000, 0);
+        deploy_vm_loader(&am, vm_address, &loader_output.encode_to_vec(0));
+        register_vm_loaders(&am, &[VmLoaderEntry::new(&WASM_MAGIC, vm_address)]);
+        let output = create_contract(&am, payload.into());
+        assert_eq!(output.exit_code, ExitCode::Ok.into_i32());
+        assert_eq!(output.address, Some(Address::with_last_byte(0x46)));
+    }
+
+    #[test]
+    fn test_calls_are_dispatched_to_registered_vm_loader() {
+        let am = JzktAccountManager::default();
+        let loader_output = EvmCallMethodOutput::from_exit_code(ExitCode::Ok)
+            .with_output(Bytes::from_static(b"ok"))
+            .with_gas(1_000, 0);
+        let (vm_address, malformed_vm_address) =
+            (Address::with_last_byte(0x42), Address::with_last_byte(0x43));
+        deploy_vm_loader(&am, vm_address, &loader_output.encode_to_vec(0));
+        deploy_vm_loader(&am, malformed_vm_address, &[0x01, 0x02]);
+        register_vm_loaders(
+            &am,
+            &[
+                VmLoaderEntry::new(&[0xfe], malformed_vm_address),
+                VmLoaderEntry::new(&[0xfe, 0xed], vm_address),
+            ],
+        );
+        let (callee, malformed_callee) =
+            (Address::with_last_byte(0x44), Address::with_last_byte(0x45));
+        for (address, source_code) in [
+            (callee, [0xfe, 0xed, 0x01]),
+            (malformed_callee, [0xfe, 0x01, 0x01]),
+        ] {
+            let source_code = Bytes::copy_from_slice(&source_code);
+            Account::new(address).update_bytecode(&am, &source_code, None, &Bytes::new(), None);
+        }
+
+        // the longest magic wins and empty code isn't matched
+        assert_eq!(resolve_vm_loader(&am, &[0xfe, 0xed]).unwrap().address, vm_address);
+        assert_eq!(resolve_vm_loader(&am, &[0xfe]).unwrap().address, malformed_vm_address);
+        assert!(resolve_vm_loader(&am, &[]).is_none());
+
+        let output = call_contract(&am, callee, 100_000);
+        assert_eq!(output.exit_code, ExitCode::Ok.into_i32());
+        assert_eq!(output.output, Bytes::from_static(b"ok"));
+        assert_eq!(output.gas_remaining, 1_000);
+        // loader can't return more gas than it was given
+        let output = call_contract(&am, callee, 999);
+        assert_eq!(output.exit_code, ExitCode::FatalExternalError.into_i32());
+        // output that can't be decoded fails the call
+        let output = call_contract(&am, malformed_callee, 100_000);
+        assert_eq!(output.exit_code, ExitCode::InputDecodeFailure.into_i32());
+    }
+}
@@ -1,6 +1,27 @@
+use fluentbase_sdk::{LowLevelAPI, LowLevelSDK};
+use fluentbase_types::{Bytes, ExitCode};
+use revm_interpreter::primitives::PrecompileError;
+
 pub mod blake2;
-pub mod bn128;
+pub mod bn128_add;
+pub mod bn128_mul;
+pub mod bn128_pair;
 pub mod identity;
 pub mod kzg_point_evaluation;
 pub mod modexp;
+pub mod ripemd160;
 pub mod secp256k1;
+pub mod sha256;
+
+/// Writes result of the precompile, output is prefixed with the gas used (u64 LE) because fuel
+/// spent on the contract execution doesn't match the precompile gas cost
+pub(crate) fn write_precompile_result(result: Result<(u64, Bytes), PrecompileError>) {
+    match result {
+        Ok((gas_used, output)) => {
+            LowLevelSDK::sys_write(&gas_used.to_le_bytes());
+            LowLevelSDK::sys_write(output.as_ref());
+        }
+        Err(PrecompileError::OutOfGas) => LowLevelSDK::sys_halt(ExitCode::OutOfFuel.into_i32()),
+        Err(_) => LowLevelSDK::sys_halt(ExitCode::PrecompileError.into_i32()),
+    }
+}
@@ -1,6 +1,5 @@
-use fluentbase_sdk::{ContextReader, ExecutionContext, LowLevelAPI, LowLevelSDK};
-use fluentbase_types::{Bytes, ExitCode};
-use revm_interpreter::primitives::PrecompileError;
+use crate::precompiles::write_precompile_result;
+use fluentbase_sdk::{ContextReader, ExecutionContext};
 
 pub fn deploy() {}
 
@@ -10,18 +9,5 @@ pub fn main() {
     let gas_limit = cr.contract_gas_limit();
 
     let result = revm_precompile::blake2::run(&input, gas_limit);
-    let result = match result {
-        Ok((_, result)) => result,
-        Err(err) => match err {
-            PrecompileError::OutOfGas => {
-                LowLevelSDK::sys_halt(ExitCode::OutOfFuel.into_i32());
-                Bytes::new()
-            }
-            _ => {
-                LowLevelSDK::sys_halt(ExitCode::PrecompileError.into_i32());
-                Bytes::new()
-            }
-        },
-    };
-    LowLevelSDK::sys_write(result.as_ref());
+    write_precompile_result(result);
 }
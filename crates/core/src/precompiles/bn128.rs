@@ -1,11 +0,0 @@
-pub fn deploy() {}
-
-pub fn main() {
-    panic!("not implemented yet")
-    // let input = ExecutionContext::contract_input();
-    // let gas_limit = ExecutionContext::contract_gas_limit();
-    //
-    // let pr = revm_precompile::bn128::run(&input, gas_limit);
-    // let res = pr.unwrap();
-    // LowLevelSDK::sys_write(res.1.as_ref());
-}
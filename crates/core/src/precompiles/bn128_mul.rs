@@ -0,0 +1,14 @@
+use crate::precompiles::write_precompile_result;
+use fluentbase_sdk::{ContextReader, ExecutionContext};
+use revm_precompile::bn128;
+
+pub fn deploy() {}
+
+pub fn main() {
+    let cr = ExecutionContext::DEFAULT;
+    let input = cr.contract_input();
+    let gas_limit = cr.contract_gas_limit();
+
+    let result = bn128::run_mul(&input, bn128::mul::ISTANBUL_MUL_GAS_COST, gas_limit);
+    write_precompile_result(result);
+}
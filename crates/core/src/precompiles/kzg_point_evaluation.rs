@@ -1,11 +1,35 @@
+use crate::precompiles::write_precompile_result;
+use fluentbase_sdk::{ContextReader, ExecutionContext, LowLevelAPI, LowLevelSDK};
+use fluentbase_types::{Bytes, ExitCode, B256};
+
+/// Fixed gas cost of the point evaluation (EIP-4844)
+const GAS_COST: u64 = 50_000;
+/// Input is `versioned_hash | z | y | commitment | proof`
+const INPUT_LENGTH: usize = 192;
+
 pub fn deploy() {}
 
 pub fn main() {
-    panic!("not implemented yet");
-    // let input = ExecutionContext::contract_input();
-    // let gas_limit = ExecutionContext::contract_gas_limit();
-    //
-    // let pr = revm_precompile::kzg_point_evaluation::run(&input, gas_limit);
-    // let res = pr.unwrap();
-    // LowLevelSDK::sys_write(res.1.as_ref());
+    let cr = ExecutionContext::DEFAULT;
+    let input = cr.contract_input();
+    let gas_limit = cr.contract_gas_limit();
+
+    if gas_limit < GAS_COST {
+        LowLevelSDK::sys_halt(ExitCode::OutOfFuel.into_i32());
+        return;
+    }
+    if input.len() != INPUT_LENGTH {
+        LowLevelSDK::sys_halt(ExitCode::PrecompileError.into_i32());
+        return;
+    }
+    // versioned hash must refer to one of the blobs attached to the transaction
+    let versioned_hash = B256::from_slice(&input[..32]);
+    if !cr.tx_blob_hashes().contains(&versioned_hash) {
+        LowLevelSDK::sys_halt(ExitCode::PrecompileError.into_i32());
+        return;
+    }
+    // pairing check is too heavy for the contract, so it's done by the host
+    let mut output = [0u8; 64];
+    LowLevelSDK::crypto_kzg_point_evaluation(input.as_ptr(), output.as_mut_ptr());
+    write_precompile_result(Ok((GAS_COST, Bytes::copy_from_slice(&output))));
 }
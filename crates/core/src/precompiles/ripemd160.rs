@@ -0,0 +1,13 @@
+use crate::precompiles::write_precompile_result;
+use fluentbase_sdk::{ContextReader, ExecutionContext};
+
+pub fn deploy() {}
+
+pub fn main() {
+    let cr = ExecutionContext::DEFAULT;
+    let input = cr.contract_input();
+    let gas_limit = cr.contract_gas_limit();
+
+    let result = revm_precompile::hash::ripemd160_run(&input, gas_limit);
+    write_precompile_result(result);
+}
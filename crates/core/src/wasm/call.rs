@@ -1,14 +1,21 @@
-use crate::debug_log;
+use crate::{debug_log, helpers::contract_input_from_context};
 use fluentbase_codec::Encoder;
 use fluentbase_sdk::{
+    Account,
     AccountManager,
     ContextReader,
-    ContractInput,
     EvmCallMethodOutput,
     WasmCallMethodInput,
     WasmCallMethodOutput,
 };
-use fluentbase_types::{ExitCode, STATE_MAIN, U256};
+use fluentbase_types::{
+    resolve_entrypoint_state,
+    ExitCode,
+    STATE_DEPLOY,
+    STATE_STATIC_FLAG,
+    STATE_UPGRADE,
+    U256,
+};
 
 pub fn _wasm_call<CR: ContextReader, AM: AccountManager>(
     cr: &CR,
@@ -32,36 +39,74 @@ pub fn _wasm_call<CR: ContextReader, AM: AccountManager>(
         return EvmCallMethodOutput::from_exit_code(ExitCode::CallDepthOverflow);
     }
 
+    // deploy entrypoint can be executed only during contract creation, and there is no bytecode
+    // upgrade flow yet that could execute the upgrade entrypoint
+    if input.state == STATE_DEPLOY || input.state == STATE_UPGRADE {
+        return WasmCallMethodOutput::from_exit_code(ExitCode::NotSupportedCall)
+            .with_gas(input.gas_limit, 0);
+    }
+
     // create new checkpoint position in the journal
     let checkpoint = am.checkpoint();
 
+    // transfer funds from caller to callee, it's reverted with the checkpoint if call fails
+    if !input.value.is_zero() {
+        let (mut caller_account, _) = am.account(cr.contract_caller());
+        let (mut callee_account, _) = am.account(cr.contract_address());
+        if caller_account.address != callee_account.address {
+            if let Err(exit_code) =
+                am.transfer(&mut caller_account, &mut callee_account, input.value)
+            {
+                am.rollback(checkpoint);
+                debug_log!("_wasm_call return: Err: exit_code: {}", exit_code);
+                return WasmCallMethodOutput::from_exit_code(exit_code)
+                    .with_gas(input.gas_limit, 0);
+            }
+            am.write_account(&caller_account);
+            am.write_account(&callee_account);
+        } else if input.value > caller_account.balance {
+            am.rollback(checkpoint);
+            return WasmCallMethodOutput::from_exit_code(ExitCode::InsufficientBalance)
+                .with_gas(input.gas_limit, 0);
+        }
+        Account::emit_transfer_log(
+            am,
+            &caller_account.address,
+            &callee_account.address,
+            &input.value,
+        );
+    }
+
     // parse callee address
     let (callee_account, _) = am.account(input.callee);
 
     let mut gas_limit = input.gas_limit as u32;
 
-    let contract_input = ContractInput {
-        journal_checkpoint: cr.journal_checkpoint().into(),
-        contract_gas_limit: gas_limit as u64,
-        contract_address: input.callee,
-        contract_caller: cr.contract_caller(),
-        contract_input: input.input,
-        tx_caller: cr.tx_caller(),
-        ..Default::default()
-    };
+    // we don't take contract callee, because callee refers to address with bytecode
+    let contract_input = contract_input_from_context(cr, gas_limit as u64, input.input);
     let contract_input_vec = contract_input.encode_to_vec(0);
 
+    // route the call to the entrypoint exported by the callee
+    let state = resolve_entrypoint_state(input.state, callee_account.entrypoint_mask);
+
+    // static calls forbid any state changes inside the callee and its nested calls
+    let state = if is_static {
+        state | STATE_STATIC_FLAG
+    } else {
+        state
+    };
+
     let bytecode_hash = callee_account.rwasm_code_hash;
     let (output_buffer, exit_code) = am.exec_hash(
         bytecode_hash.as_ptr(),
         &contract_input_vec,
         &mut gas_limit as *mut u32,
-        STATE_MAIN,
+        state,
     );
 
-    // if exit code success then commit changes, otherwise rollback
+    // if exit code success then merge changes, otherwise rollback
     if ExitCode::from(exit_code).is_ok() {
-        am.commit();
+        am.checkpoint_merge();
     } else {
         am.rollback(checkpoint);
     }
@@ -1,4 +1,16 @@
-use crate::{debug_log, helpers::wasm2rwasm};
+use crate::{
+    consts::{
+        RWASM_CODE_DEPOSIT_BYTE_COST,
+        WASM_CODE_DEPOSIT_BYTE_COST,
+        WASM_MAX_INITCODE_SIZE,
+        WASM_TRANSLATION_WORD_COST,
+        WASM_VALIDATION_WORD_COST,
+    },
+    debug_log,
+    helpers::{contract_input_from_context, split_wasm_initcode, wasm2rwasm},
+    wasm::validator::validate_wasm_module,
+};
+use fluentbase_codec::Encoder;
 use fluentbase_sdk::{
     Account,
     AccountManager,
@@ -9,6 +21,7 @@ use fluentbase_sdk::{
     WasmCreateMethodOutput,
 };
 use fluentbase_types::{Bytes, ExitCode, B256, STATE_DEPLOY};
+use revm_interpreter::Gas;
 use revm_primitives::RWASM_MAX_CODE_SIZE;
 
 pub fn _wasm_create<CR: ContextReader, AM: AccountManager>(
@@ -18,27 +31,62 @@ pub fn _wasm_create<CR: ContextReader, AM: AccountManager>(
 ) -> WasmCreateMethodOutput {
     debug_log!("_wasm_create start");
 
-    // TODO: "gas calculations"
-    // TODO: "call depth stack check >= 1024"
-
     // check write protection
     if cr.contract_is_static() {
         debug_log!(
             "_wasm_create return: Err: exit_code: {}",
             ExitCode::WriteProtection
         );
-        return WasmCreateMethodOutput::from_exit_code(ExitCode::WriteProtection);
+        return WasmCreateMethodOutput::from_exit_code(ExitCode::WriteProtection)
+            .with_gas(input.gas_limit, 0);
+    }
+
+    // call depth check
+    if input.depth > 1024 {
+        return WasmCreateMethodOutput::from_exit_code(ExitCode::CallDepthOverflow)
+            .with_gas(input.gas_limit, 0);
     }
 
-    // code length can't exceed max constructor limit
-    if input.bytecode.len() > RWASM_MAX_CODE_SIZE {
+    // deploy payload is a WASM module with optional length-prefixed constructor arguments
+    let (wasm_bytecode, constructor_args) = match split_wasm_initcode(&input.bytecode) {
+        Ok(result) => result,
+        Err(exit_code) => {
+            return WasmCreateMethodOutput::from_exit_code(exit_code).with_gas(input.gas_limit, 0);
+        }
+    };
+
+    // deploy payload can't exceed initcode limit and WASM module can't exceed max code size
+    if input.bytecode.len() > WASM_MAX_INITCODE_SIZE || wasm_bytecode.len() > RWASM_MAX_CODE_SIZE {
         debug_log!(
             "_wasm_create return: Err: exit_code: {}",
             ExitCode::ContractSizeLimit
         );
-        return WasmCreateMethodOutput::from_exit_code(ExitCode::ContractSizeLimit);
+        return WasmCreateMethodOutput::from_exit_code(ExitCode::ContractSizeLimit)
+            .with_gas(input.gas_limit, 0);
     }
 
+    // charge validation and translation cost in proportion to the module size
+    let mut gas = Gas::new(input.gas_limit);
+    let wasm_words = (wasm_bytecode.len() as u64 + 31) / 32;
+    if !gas.record_cost(wasm_words * (WASM_VALIDATION_WORD_COST + WASM_TRANSLATION_WORD_COST)) {
+        return WasmCreateMethodOutput::from_exit_code(ExitCode::OutOfFuel);
+    }
+
+    // reject modules that can't be translated or executed before touching the state
+    let entrypoints = match validate_wasm_module(wasm_bytecode) {
+        Ok(entrypoints) => entrypoints,
+        Err(err) => {
+            debug_log!(
+                "_wasm_create return: Err: exit_code: {} reason: {}",
+                err.exit_code,
+                err.reason
+            );
+            return WasmCreateMethodOutput::from_exit_code(err.exit_code)
+                .with_output(err.reason.into_bytes().into())
+                .with_gas(gas.remaining(), 0);
+        }
+    };
+
     let mut source_code_hash: B256 = B256::ZERO;
     LowLevelSDK::crypto_keccak256(
         input.bytecode.as_ptr(),
@@ -60,7 +108,8 @@ pub fn _wasm_create<CR: ContextReader, AM: AccountManager>(
     ) {
         Ok(result) => result,
         Err(exit_code) => {
-            return WasmCreateMethodOutput::from_exit_code(exit_code);
+            return WasmCreateMethodOutput::from_exit_code(exit_code)
+                .with_gas(gas.remaining(), 0);
         }
     };
     if !input.value.is_zero() {
@@ -79,7 +128,7 @@ pub fn _wasm_create<CR: ContextReader, AM: AccountManager>(
     );
 
     // translate WASM to rWASM
-    let rwasm_bytecode = match wasm2rwasm(&input.bytecode) {
+    let rwasm_bytecode = match wasm2rwasm(wasm_bytecode) {
         Ok(result) => result,
         Err(exit_code) => {
             am.rollback(checkpoint);
@@ -87,6 +136,19 @@ pub fn _wasm_create<CR: ContextReader, AM: AccountManager>(
             return WasmCreateMethodOutput::from_exit_code(exit_code);
         }
     };
+    if rwasm_bytecode.len() > RWASM_MAX_CODE_SIZE {
+        am.rollback(checkpoint);
+        return WasmCreateMethodOutput::from_exit_code(ExitCode::ContractSizeLimit)
+            .with_gas(gas.remaining(), 0);
+    }
+
+    // record gas for each stored byte of both source and rWASM bytecode
+    let gas_for_code = wasm_bytecode.len() as u64 * WASM_CODE_DEPOSIT_BYTE_COST
+        + rwasm_bytecode.len() as u64 * RWASM_CODE_DEPOSIT_BYTE_COST;
+    if !gas.record_cost(gas_for_code) {
+        am.rollback(checkpoint);
+        return WasmCreateMethodOutput::from_exit_code(ExitCode::OutOfFuel);
+    }
     // let exit_code = LowLevelSDK::wasm_to_rwasm(
     //     input.bytecode.as_ptr(),
     //     input.bytecode.len() as u32,
@@ -107,21 +169,43 @@ pub fn _wasm_create<CR: ContextReader, AM: AccountManager>(
     // write deployer to the trie
     am.write_account(&deployer_account);
 
-    // write contract to the trie
-    contract_account.update_bytecode(am, &input.bytecode, None, &rwasm_bytecode.into(), None);
+    // write contract to the trie, exported entrypoints are kept in the account, so calls can be
+    // routed to `receive` or `fallback`
+    contract_account.entrypoint_mask = entrypoints;
+    contract_account.update_bytecode(
+        am,
+        &Bytes::copy_from_slice(wasm_bytecode),
+        None,
+        &rwasm_bytecode.into(),
+        None,
+    );
 
-    let mut gas_limit = input.gas_limit as u32;
-    let (_, exit_code) = am.exec_hash(
+    // fuel is limited by u32, so the rest of the gas stays in the frame
+    let fuel_limit = gas.remaining().min(u32::MAX as u64) as u32;
+    let mut fuel = fuel_limit;
+    // constructor arguments are passed to the deploy entrypoint as contract input
+    let mut contract_input = contract_input_from_context(
+        cr,
+        fuel_limit as u64,
+        Bytes::copy_from_slice(constructor_args),
+    );
+    contract_input.contract_address = contract_account.address;
+    contract_input.contract_caller = caller_address;
+    contract_input.contract_value = input.value;
+    let (output, exit_code) = am.exec_hash(
         contract_account.rwasm_code_hash.as_ptr(),
-        &[],
-        &mut gas_limit as *mut u32,
+        &contract_input.encode_to_vec(0),
+        &mut fuel as *mut u32,
         STATE_DEPLOY,
     );
+    gas.record_cost((fuel_limit - fuel) as u64);
     // if call is not success set deployed address to zero
     if exit_code != ExitCode::Ok.into_i32() {
         am.rollback(checkpoint);
         debug_log!("_wasm_create return: Err: ExitCode::TransactError");
-        return WasmCreateMethodOutput::from_exit_code(ExitCode::from(exit_code));
+        return WasmCreateMethodOutput::from_exit_code(ExitCode::from(exit_code))
+            .with_output(output.into())
+            .with_gas(gas.remaining(), 0);
     }
 
     debug_log!(
@@ -129,14 +213,14 @@ pub fn _wasm_create<CR: ContextReader, AM: AccountManager>(
         contract_account.address
     );
 
-    // commit all changes made
-    am.commit();
+    // merge all changes made into the parent frame
+    am.checkpoint_merge();
 
     WasmCreateMethodOutput {
-        output: Bytes::new(),
+        output: output.into(),
         address: Some(contract_account.address),
         exit_code,
-        gas: gas_limit as u64,
-        gas_refund: 0,
+        gas: gas.remaining(),
+        gas_refund: gas.refunded(),
     }
 }
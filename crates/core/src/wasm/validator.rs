@@ -0,0 +1,284 @@
+use crate::consts::{WASM_MAX_MEMORY_PAGES, WASM_MAX_TABLE_SIZE};
+use alloc::{format, string::String};
+use fluentbase_types::{
+    entrypoint_mask,
+    ExitCode,
+    SOVEREIGN_IMPORTS,
+    STATE_DEPLOY,
+    STATE_MAIN,
+    STATE_ROUTER_ENTRYPOINTS,
+};
+use wasmparser::{
+    BinaryReaderError,
+    ExternalKind,
+    Parser,
+    Payload,
+    TypeRef,
+    Validator,
+    WasmFeatures,
+};
+
+/// Reason why WASM module can't be deployed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasmValidationError {
+    pub exit_code: ExitCode,
+    pub reason: String,
+}
+
+impl WasmValidationError {
+    fn new(exit_code: ExitCode, reason: String) -> Self {
+        Self { exit_code, reason }
+    }
+}
+
+/// Checks that WASM module can be translated into rWASM and executed by the runtime.
+///
+/// The module must be well-formed, must not use floats, SIMD, threads or multiple memories,
+/// can import only sovereign host functions and must export `deploy` and `main` entrypoints.
+/// Returns the mask of exported entrypoints (see `STATE_ROUTER_ENTRYPOINTS`).
+pub fn validate_wasm_module(wasm_binary: &[u8]) -> Result<u32, WasmValidationError> {
+    validate_features(wasm_binary)?;
+
+    let mut entrypoints = 0u32;
+    for payload in Parser::new(0).parse_all(wasm_binary) {
+        match payload.map_err(malformed)? {
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    let import = import.map_err(malformed)?;
+                    if !matches!(import.ty, TypeRef::Func(_)) {
+                        return Err(WasmValidationError::new(
+                            ExitCode::UnsupportedWasmFeature,
+                            format!(
+                                "only function imports are supported: {}::{}",
+                                import.module, import.name
+                            ),
+                        ));
+                    }
+                    let is_known = SOVEREIGN_IMPORTS.iter().any(|(module, name, _, _)| {
+                        *module == import.module && *name == import.name
+                    });
+                    if !is_known {
+                        return Err(WasmValidationError::new(
+                            ExitCode::UnknownImport,
+                            format!("unknown import: {}::{}", import.module, import.name),
+                        ));
+                    }
+                }
+            }
+            Payload::MemorySection(reader) => {
+                for memory in reader {
+                    let memory = memory.map_err(malformed)?;
+                    let pages = memory.maximum.unwrap_or(memory.initial).max(memory.initial);
+                    if pages > WASM_MAX_MEMORY_PAGES {
+                        return Err(WasmValidationError::new(
+                            ExitCode::WasmLimitExceeded,
+                            format!(
+                                "memory size {} exceeds limit of {} pages",
+                                pages, WASM_MAX_MEMORY_PAGES
+                            ),
+                        ));
+                    }
+                }
+            }
+            Payload::TableSection(reader) => {
+                for table in reader {
+                    let table = table.map_err(malformed)?;
+                    let size = table.maximum.unwrap_or(table.initial).max(table.initial);
+                    if size > WASM_MAX_TABLE_SIZE {
+                        return Err(WasmValidationError::new(
+                            ExitCode::WasmLimitExceeded,
+                            format!(
+                                "table size {} exceeds limit of {} elements",
+                                size, WASM_MAX_TABLE_SIZE
+                            ),
+                        ));
+                    }
+                }
+            }
+            Payload::ExportSection(reader) => {
+                for export in reader {
+                    let export = export.map_err(malformed)?;
+                    if export.kind != ExternalKind::Func {
+                        continue;
+                    }
+                    if let Some((_, state)) = STATE_ROUTER_ENTRYPOINTS
+                        .iter()
+                        .find(|(name, _)| *name == export.name)
+                    {
+                        entrypoints |= entrypoint_mask(*state);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    for (name, state) in [("deploy", STATE_DEPLOY), ("main", STATE_MAIN)] {
+        if entrypoints & entrypoint_mask(state) == 0 {
+            return Err(WasmValidationError::new(
+                ExitCode::MissingEntrypoint,
+                format!("missing exported function: {}", name),
+            ));
+        }
+    }
+    Ok(entrypoints)
+}
+
+/// Returns the mask of entrypoints exported by the WASM module (see `STATE_ROUTER_ENTRYPOINTS`),
+/// it's empty for malformed modules
+pub fn wasm_entrypoint_mask(wasm_binary: &[u8]) -> u32 {
+    let mut entrypoints = 0u32;
+    for payload in Parser::new(0).parse_all(wasm_binary) {
+        let Ok(Payload::ExportSection(reader)) = payload else {
+            continue;
+        };
+        for export in reader.into_iter().flatten() {
+            if export.kind != ExternalKind::Func {
+                continue;
+            }
+            if let Some((_, state)) = STATE_ROUTER_ENTRYPOINTS
+                .iter()
+                .find(|(name, _)| *name == export.name)
+            {
+                entrypoints |= entrypoint_mask(*state);
+            }
+        }
+    }
+    entrypoints
+}
+
+/// Validates module with the supported feature set, extra features are enabled only to explain
+/// why the module is rejected
+fn validate_features(wasm_binary: &[u8]) -> Result<(), WasmValidationError> {
+    let mut features = WasmFeatures {
+        floats: false,
+        simd: false,
+        relaxed_simd: false,
+        threads: false,
+        multi_memory: false,
+        ..Default::default()
+    };
+    let Err(err) = Validator::new_with_features(features).validate_all(wasm_binary) else {
+        return Ok(());
+    };
+    features.floats = true;
+    let exit_code = if Validator::new_with_features(features)
+        .validate_all(wasm_binary)
+        .is_ok()
+    {
+        ExitCode::FloatInstructionsNotAllowed
+    } else {
+        features.simd = true;
+        features.relaxed_simd = true;
+        features.threads = true;
+        features.multi_memory = true;
+        match Validator::new_with_features(features).validate_all(wasm_binary) {
+            Ok(_) => ExitCode::UnsupportedWasmFeature,
+            Err(_) => ExitCode::MalformedWasmModule,
+        }
+    };
+    Err(WasmValidationError::new(exit_code, format!("{}", err)))
+}
+
+fn malformed(err: BinaryReaderError) -> WasmValidationError {
+    WasmValidationError::new(ExitCode::MalformedWasmModule, format!("{}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_wasm_module;
+    use fluentbase_types::{
+        entrypoint_mask,
+        ExitCode,
+        STATE_DEPLOY,
+        STATE_FALLBACK,
+        STATE_MAIN,
+        STATE_RECEIVE,
+    };
+
+    fn validate_wat(wat: &str) -> Result<u32, ExitCode> {
+        let wasm_binary = wat::parse_str(wat).unwrap();
+        validate_wasm_module(&wasm_binary).map_err(|err| err.exit_code)
+    }
+
+    #[test]
+    fn test_validate_wasm_module() {
+        let wasm_binary = include_bytes!("../../../../examples/bin/greeting.wasm");
+        let required = entrypoint_mask(STATE_DEPLOY) | entrypoint_mask(STATE_MAIN);
+        assert_eq!(validate_wasm_module(wasm_binary), Ok(required));
+        assert_eq!(
+            validate_wasm_module(&[0x00, 0x61, 0x73]).unwrap_err().exit_code,
+            ExitCode::MalformedWasmModule
+        );
+        assert_eq!(
+            validate_wat(
+                r#"(module
+                    (import "fluentbase_v1alpha" "_sys_halt" (func (param i32)))
+                    (memory 1)
+                    (func (export "deploy"))
+                    (func (export "main")))"#
+            ),
+            Ok(required)
+        );
+        assert_eq!(
+            validate_wat(
+                r#"(module
+                    (func (export "deploy"))
+                    (func (export "main"))
+                    (func (export "receive"))
+                    (func (export "fallback"))
+                    (func (export "helper")))"#
+            ),
+            Ok(required | entrypoint_mask(STATE_RECEIVE) | entrypoint_mask(STATE_FALLBACK))
+        );
+        assert_eq!(
+            validate_wat(
+                r#"(module
+                    (func (export "deploy"))
+                    (func (export "main") (drop (f32.const 1))))"#
+            ),
+            Err(ExitCode::FloatInstructionsNotAllowed)
+        );
+        assert_eq!(
+            validate_wat(
+                r#"(module
+                    (memory 1 1 shared)
+                    (func (export "deploy"))
+                    (func (export "main")))"#
+            ),
+            Err(ExitCode::UnsupportedWasmFeature)
+        );
+        assert_eq!(
+            validate_wat(
+                r#"(module
+                    (import "env" "_sys_halt" (func (param i32)))
+                    (func (export "deploy"))
+                    (func (export "main")))"#
+            ),
+            Err(ExitCode::UnknownImport)
+        );
+        // KZG pairing check is available for system contracts only
+        assert_eq!(
+            validate_wat(
+                r#"(module
+                    (import "fluentbase_v1alpha" "_crypto_kzg_point_evaluation"
+                        (func (param i32 i32)))
+                    (func (export "deploy"))
+                    (func (export "main")))"#
+            ),
+            Err(ExitCode::UnknownImport)
+        );
+        assert_eq!(
+            validate_wat(r#"(module (func (export "main")))"#),
+            Err(ExitCode::MissingEntrypoint)
+        );
+        assert_eq!(
+            validate_wat(
+                r#"(module
+                    (memory 2048)
+                    (func (export "deploy"))
+                    (func (export "main")))"#
+            ),
+            Err(ExitCode::WasmLimitExceeded)
+        );
+    }
+}
@@ -1,7 +1,36 @@
-use crate::{ChainConfig, Genesis, GenesisAccount, EXAMPLE_GREETING_ADDRESS};
-use fluentbase_core::consts::{ECL_CONTRACT_ADDRESS, WCL_CONTRACT_ADDRESS};
+use crate::{
+    vm_registry_genesis_account,
+    ChainConfig,
+    Genesis,
+    GenesisAccount,
+    EXAMPLE_GREETING_ADDRESS,
+};
+use fluentbase_core::consts::{
+    ECL_CONTRACT_ADDRESS,
+    PRECOMPILE_BLAKE2_ADDRESS,
+    PRECOMPILE_BN128_ADD_ADDRESS,
+    PRECOMPILE_BN128_MUL_ADDRESS,
+    PRECOMPILE_BN128_PAIR_ADDRESS,
+    PRECOMPILE_IDENTITY_ADDRESS,
+    PRECOMPILE_KZG_POINT_EVALUATION_ADDRESS,
+    PRECOMPILE_MODEXP_ADDRESS,
+    PRECOMPILE_RIPEMD160_ADDRESS,
+    PRECOMPILE_SECP256K1_ADDRESS,
+    PRECOMPILE_SHA256_ADDRESS,
+    VM_REGISTRY_ADDRESS,
+    WCL_CONTRACT_ADDRESS,
+};
 use fluentbase_poseidon::poseidon_hash;
-use fluentbase_types::{address, b256, Address, Bytes, B256, U256};
+use fluentbase_types::{
+    address,
+    b256,
+    Address,
+    Bytes,
+    VmLoaderEntry,
+    B256,
+    U256,
+    WASM_MAGIC,
+};
 use revm_primitives::keccak256;
 use std::collections::BTreeMap;
 
@@ -34,6 +63,11 @@ pub fn devnet_chain_config() -> ChainConfig {
     }
 }
 
+/// VM loaders registered in the devnet genesis: WASM is executed by the WCL, EOF isn't supported
+pub fn devnet_vm_loaders() -> Vec<VmLoaderEntry> {
+    vec![VmLoaderEntry::new(&WASM_MAGIC, WCL_CONTRACT_ADDRESS)]
+}
+
 /// Keccak256("poseidon_hash_key")
 pub const POSEIDON_HASH_KEY: B256 =
     b256!("72adc1368da53d255ed52bce3690fa2b9ec0f64072bcdf3c86adcaf50b54cff1");
@@ -113,34 +147,55 @@ pub fn devnet_genesis() -> Genesis {
         WCL_CONTRACT_ADDRESS,
         "../../contracts/assets/wcl_contract.rwasm"
     );
-    // enable_rwasm_contract!(
-    //     PRECOMPILE_BLAKE2_ADDRESS,
-    //     "../../contracts/assets/precompile_blake2.rwasm"
-    // );
-    // enable_rwasm_contract!(
-    //     PRECOMPILE_BN128_ADDRESS,
-    //     "../../contracts/assets/precompile_bn128.rwasm"
-    // );
-    // enable_rwasm_contract!(
-    //     PRECOMPILE_IDENTITY_ADDRESS,
-    //     "../../contracts/assets/precompile_identity.rwasm"
-    // );
-    // enable_rwasm_contract!(
-    //     PRECOMPILE_KZG_POINT_EVALUATION_ADDRESS,
-    //     "../../contracts/assets/precompile_kzg_point_evaluation.rwasm"
-    // );
-    // enable_rwasm_contract!(
-    //     PRECOMPILE_MODEXP_ADDRESS,
-    //     "../../contracts/assets/precompile_modexp.rwasm"
-    // );
-    // enable_rwasm_contract!(
-    //     PRECOMPILE_SECP256K1_ADDRESS,
-    //     "../../contracts/assets/precompile_secp256k1.rwasm"
-    // );
+    enable_rwasm_contract!(
+        PRECOMPILE_SECP256K1_ADDRESS,
+        "../../contracts/assets/precompile_secp256k1.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_SHA256_ADDRESS,
+        "../../contracts/assets/precompile_sha256.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_RIPEMD160_ADDRESS,
+        "../../contracts/assets/precompile_ripemd160.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_IDENTITY_ADDRESS,
+        "../../contracts/assets/precompile_identity.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_MODEXP_ADDRESS,
+        "../../contracts/assets/precompile_modexp.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_BN128_ADD_ADDRESS,
+        "../../contracts/assets/precompile_bn128_add.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_BN128_MUL_ADDRESS,
+        "../../contracts/assets/precompile_bn128_mul.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_BN128_PAIR_ADDRESS,
+        "../../contracts/assets/precompile_bn128_pair.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_BLAKE2_ADDRESS,
+        "../../contracts/assets/precompile_blake2.rwasm"
+    );
+    enable_rwasm_contract!(
+        PRECOMPILE_KZG_POINT_EVALUATION_ADDRESS,
+        "../../contracts/assets/precompile_kzg_point_evaluation.rwasm"
+    );
     enable_rwasm_contract!(
         EXAMPLE_GREETING_ADDRESS,
         "../../../examples/bin/greeting.rwasm"
     );
+    // built-in VMs, legacy EVM bytecode has no magic, so it's still executed by the ECL directly
+    alloc.insert(
+        VM_REGISTRY_ADDRESS,
+        vm_registry_genesis_account(&devnet_vm_loaders()),
+    );
     Genesis {
         config: devnet_chain_config(),
         nonce: 0,
@@ -1,7 +1,99 @@
 pub use alloy_genesis::{ChainConfig, Genesis, GenesisAccount};
-use fluentbase_types::{address, Address};
+use fluentbase_types::{address, Address, VmLoaderEntry, B256, U256};
+use revm_primitives::SpecId;
+use std::collections::BTreeMap;
 
 pub mod devnet;
 
 // example
 pub const EXAMPLE_GREETING_ADDRESS: Address = address!("5300000000000000000000000000000000000001");
+
+/// Converts chain config into the hardfork schedule that is passed to ECL through the context,
+/// forks before Shanghai are activated by block number and the later ones by block timestamp.
+pub fn chain_config_hardforks(config: &ChainConfig) -> Vec<(u8, u64)> {
+    [
+        (SpecId::FRONTIER, Some(0)),
+        (SpecId::HOMESTEAD, config.homestead_block),
+        (SpecId::DAO_FORK, config.dao_fork_block),
+        (SpecId::TANGERINE, config.eip150_block),
+        (SpecId::SPURIOUS_DRAGON, config.eip158_block),
+        (SpecId::BYZANTIUM, config.byzantium_block),
+        (SpecId::CONSTANTINOPLE, config.constantinople_block),
+        (SpecId::PETERSBURG, config.petersburg_block),
+        (SpecId::ISTANBUL, config.istanbul_block),
+        (SpecId::MUIR_GLACIER, config.muir_glacier_block),
+        (SpecId::BERLIN, config.berlin_block),
+        (SpecId::LONDON, config.london_block),
+        (SpecId::ARROW_GLACIER, config.arrow_glacier_block),
+        (SpecId::GRAY_GLACIER, config.gray_glacier_block),
+        (SpecId::MERGE, config.merge_netsplit_block),
+        (SpecId::SHANGHAI, config.shanghai_time),
+        (SpecId::CANCUN, config.cancun_time),
+    ]
+    .into_iter()
+    .filter_map(|(spec_id, activation)| Some((spec_id as u8, activation?)))
+    .collect()
+}
+
+/// Creates genesis account of the VM registry (`VM_REGISTRY_ADDRESS`) with the VM loaders, slot
+/// `0` keeps the number of loaders and the rest keep the loaders one by one.
+pub fn vm_registry_genesis_account(vm_loaders: &[VmLoaderEntry]) -> GenesisAccount {
+    let mut storage = BTreeMap::from([(B256::ZERO, U256::from(vm_loaders.len()).into())]);
+    for (i, vm_loader) in vm_loaders.iter().enumerate() {
+        storage.insert(U256::from(i + 1).into(), vm_loader.to_storage_value().into());
+    }
+    GenesisAccount {
+        storage: Some(storage),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        chain_config_hardforks,
+        devnet::{devnet_chain_config, devnet_genesis, devnet_vm_loaders},
+        vm_registry_genesis_account,
+    };
+    use fluentbase_core::consts::{VM_REGISTRY_ADDRESS, WCL_CONTRACT_ADDRESS};
+    use fluentbase_types::{address, VmLoaderEntry, B256, U256};
+    use revm_primitives::SpecId;
+
+    #[test]
+    fn test_devnet_hardforks() {
+        let hardforks = chain_config_hardforks(&devnet_chain_config());
+        assert!(hardforks.iter().all(|(_, activation)| *activation == 0));
+        assert_eq!(hardforks.last(), Some(&(SpecId::CANCUN as u8, 0)));
+    }
+
+    #[test]
+    fn test_vm_registry_genesis_account() {
+        let vm_loader = VmLoaderEntry::new(
+            &[0xef, 0x52],
+            address!("5200000000000000000000000000000000000010"),
+        );
+        let account = vm_registry_genesis_account(&[vm_loader.clone()]);
+        let storage = account.storage.unwrap();
+        assert_eq!(storage[&B256::ZERO], B256::from(U256::from(1)));
+        let value = U256::from_be_bytes(storage[&B256::from(U256::from(1))].0);
+        assert_eq!(VmLoaderEntry::from_storage_value(value), Some(vm_loader.clone()));
+        assert!(vm_loader.matches(&[0xef, 0x52, 0x00]));
+        assert!(!vm_loader.matches(&[0xef, 0x00]));
+    }
+
+    #[test]
+    fn test_devnet_vm_registry() {
+        let genesis = devnet_genesis();
+        let storage = genesis.alloc[&VM_REGISTRY_ADDRESS].storage.clone().unwrap();
+        let vm_loaders = devnet_vm_loaders();
+        assert_eq!(storage[&B256::ZERO], B256::from(U256::from(vm_loaders.len())));
+        let vm_loader = |i: usize| {
+            let value = U256::from_be_bytes(storage[&B256::from(U256::from(i))].0);
+            VmLoaderEntry::from_storage_value(value).unwrap()
+        };
+        assert_eq!(vm_loader(1).address, WCL_CONTRACT_ADDRESS);
+        assert!(vm_loader(1).matches(include_bytes!("../../../examples/bin/greeting.wasm")));
+        // EOF isn't executed, so it's not routed to the ECL
+        assert_eq!(vm_loaders.len(), 1);
+    }
+}
@@ -36,7 +36,7 @@ use fluentbase_core::{
     evm::{call::_evm_call, create::_evm_create, sload::_evm_sload, sstore::_evm_sstore},
     fluent_host::FluentHost,
     loader::{_loader_call, _loader_create},
-    wasm::{call::_wasm_call, create::_wasm_create},
+    wasm::{call::_wasm_call, create::_wasm_create, validator::wasm_entrypoint_mask},
 };
 use fluentbase_sdk::{
     Account,
@@ -65,6 +65,7 @@ use fluentbase_sdk::{
 };
 use fluentbase_types::{
     address,
+    call_entrypoint_state,
     BytecodeType,
     Bytes,
     Bytes32,
@@ -75,7 +76,7 @@ use fluentbase_types::{
     NATIVE_TRANSFER_ADDRESS,
     NATIVE_TRANSFER_KECCAK,
     POSEIDON_EMPTY,
-    STATE_MAIN,
+    STATE_STATIC_FLAG,
 };
 use revm_primitives::{hex, Bytecode, CreateScheme, Env, Log, LogData};
 use std as alloc;
@@ -451,12 +452,14 @@ impl<EXT, DB: Database> Evm<'_, EXT, DB> {
             self.context.evm.journaled_state.touch(&callee_address);
         }
 
+        let state = call_entrypoint_state(&input, &value);
         let method_input = EvmCallMethodInput {
             callee: callee_address,
             value,
             input,
             gas_limit: gas.remaining(),
             depth: 0,
+            state,
         };
         let contract_input = self.input_from_env(
             &mut gas,
@@ -531,13 +534,22 @@ impl<EXT, DB: Database> Evm<'_, EXT, DB> {
     }
 
     fn input_from_env(
-        &self,
+        &mut self,
         gas: &Gas,
         caller_address: Address,
         callee_address: Address,
         input: Bytes,
         value: U256,
     ) -> ContractInput {
+        let block_number = self.context.evm.env.block.number;
+        let block_parent_hash = if block_number > U256::ZERO {
+            self.context
+                .evm
+                .block_hash(block_number - U256::from(1))
+                .unwrap_or_default()
+        } else {
+            B256::ZERO
+        };
         ContractInput {
             journal_checkpoint: 0,
             contract_gas_limit: gas.remaining(),
@@ -553,6 +565,9 @@ impl<EXT, DB: Database> Evm<'_, EXT, DB> {
             block_difficulty: self.context.evm.env.block.difficulty.as_limbs()[0],
             block_gas_limit: self.context.evm.env.block.gas_limit.as_limbs()[0],
             block_base_fee: self.context.evm.env.block.basefee,
+            block_parent_hash,
+            // spec is already resolved for the current block
+            block_hardforks: vec![(self.spec_id() as u8, 0)],
             tx_gas_limit: self.context.evm.env.tx.gas_limit,
             tx_nonce: self.context.evm.env.tx.nonce.unwrap_or_default(),
             tx_gas_price: self.context.evm.env.tx.gas_price,
@@ -701,8 +716,20 @@ impl<'a, DB: Database> IJournaledTrie for JournalDbWrapper<'a, DB> {
         }
     }
 
-    fn remove(&self, _key: &[u8; 32]) {
-        // TODO: "account removal is not supported"
+    fn remove(&self, key: &[u8; 32]) {
+        if key[..12] == [0u8; 12] {
+            // revm can't remove accounts directly, but empty account is cleared at the end of
+            // transaction (EIP-161)
+            let address = Address::from_slice(&key[12..]);
+            AccountManager::write_account(self, &Account::new(address));
+        } else {
+            AccountManager::write_storage(
+                self,
+                EVM_STORAGE_ADDRESS,
+                U256::from_le_bytes(*key),
+                U256::ZERO,
+            );
+        }
     }
 
     fn compute_root(&self) -> [u8; 32] {
@@ -728,11 +755,12 @@ impl<'a, DB: Database> IJournaledTrie for JournalDbWrapper<'a, DB> {
         true
     }
 
-    fn preimage(&self, hash: &[u8; 32]) -> Vec<u8> {
+    fn preimage(&self, _field: u32, hash: &[u8; 32]) -> Vec<u8> {
+        // revm keeps only bytecode preimages that are keyed by the code hash
         AccountManager::preimage(self, hash).to_vec()
     }
 
-    fn preimage_size(&self, hash: &[u8; 32]) -> u32 {
+    fn preimage_size(&self, _field: u32, hash: &[u8; 32]) -> u32 {
         AccountManager::preimage_size(self, hash)
     }
 
@@ -740,6 +768,29 @@ impl<'a, DB: Database> IJournaledTrie for JournalDbWrapper<'a, DB> {
         // TODO: "journal is not supported here"
         vec![]
     }
+
+    fn access(&self, key: &[u8; 32]) -> bool {
+        // revm warms accounts and slots on load, so reading the key is enough
+        self.get(key, false).map(|(_, _, is_cold)| is_cold).unwrap_or(true)
+    }
+
+    fn transient_get(&self, key: &[u8; 32]) -> Option<[u8; 32]> {
+        let value = AccountManager::transient_storage(
+            self,
+            EVM_STORAGE_ADDRESS,
+            U256::from_le_bytes(*key),
+        );
+        Some(value.to_le_bytes::<32>())
+    }
+
+    fn transient_update(&self, key: &[u8; 32], value: &[u8; 32]) {
+        AccountManager::write_transient_storage(
+            self,
+            EVM_STORAGE_ADDRESS,
+            U256::from_le_bytes(*key),
+            U256::from_le_bytes(*value),
+        );
+    }
 }
 
 impl<'a, DB: Database> AccountManager for JournalDbWrapper<'a, DB> {
@@ -749,11 +800,16 @@ impl<'a, DB: Database> AccountManager for JournalDbWrapper<'a, DB> {
         fluentbase_types::JournalCheckpoint::from((a, b)).to_u64()
     }
 
-    fn commit(&self) {
+    fn checkpoint_merge(&self) {
         let mut ctx = self.ctx.borrow_mut();
         ctx.journaled_state.checkpoint_commit();
     }
 
+    fn commit(&self) {
+        // state is committed by revm itself at the end of transaction
+        AccountManager::checkpoint_merge(self);
+    }
+
     fn rollback(&self, checkpoint: AccountCheckpoint) {
         let checkpoint = fluentbase_types::JournalCheckpoint::from_u64(checkpoint);
         let mut ctx = self.ctx.borrow_mut();
@@ -763,9 +819,19 @@ impl<'a, DB: Database> AccountManager for JournalDbWrapper<'a, DB> {
 
     fn account(&self, address: Address) -> (Account, bool) {
         let mut ctx = self.ctx.borrow_mut();
-        let (account, is_cold) = ctx.load_account(address).expect("database error");
+        let (account, is_cold) = ctx.load_account_with_code(address).expect("database error");
+        // revm doesn't store the entrypoint mask, so we derive it from the WASM source code
+        let entrypoint_mask = account
+            .info
+            .code
+            .as_ref()
+            .map(|code| code.original_bytes())
+            .filter(|code| BytecodeType::from_slice(code) == BytecodeType::WASM)
+            .map(|code| wasm_entrypoint_mask(&code))
+            .unwrap_or_default();
         let mut account = Account::from(account.info.clone());
         account.address = address;
+        account.entrypoint_mask = entrypoint_mask;
         (account, is_cold)
     }
 
@@ -828,8 +894,14 @@ impl<'a, DB: Database> AccountManager for JournalDbWrapper<'a, DB> {
             (address, slot)
         };
         if committed {
-            let (account, _) = ctx.load_account(address).expect("failed to load account");
-            if account.is_created() {
+            // committed reads must not warm the account, and accounts that aren't loaded yet
+            // can't be created in this transaction
+            let is_created = ctx
+                .journaled_state
+                .state
+                .get(&address)
+                .is_some_and(|account| account.is_created());
+            if is_created {
                 return (U256::ZERO, true);
             }
             let value = ctx
@@ -891,7 +963,8 @@ impl<'a, DB: Database> AccountManager for JournalDbWrapper<'a, DB> {
             .with_input(input.into())
             .with_fuel_limit(unsafe { *fuel_offset } as u64)
             .with_jzkt(jzkt)
-            .with_state(state);
+            .with_state(state & !STATE_STATIC_FLAG)
+            .with_is_static(state & STATE_STATIC_FLAG != 0);
         let mut runtime = Runtime::new(ctx);
         let result = match runtime.call() {
             Ok(result) => result,
@@ -951,17 +1024,21 @@ impl<'a, DB: Database> AccountManager for JournalDbWrapper<'a, DB> {
             .contains(address)
     }
 
-    fn self_destruct(&self, address: Address, target: Address) -> [bool; 4] {
+    fn self_destruct(
+        &self,
+        address: Address,
+        target: Address,
+    ) -> Result<[bool; 4], ExitCode> {
         let mut ctx = self.ctx.borrow_mut();
         let result = ctx
             .selfdestruct(address, target)
-            .expect("unexpected EVM self destruct error");
-        [
+            .map_err(|_| ExitCode::FatalExternalError)?;
+        Ok([
             result.had_value,
             result.target_exists,
             result.is_cold,
             result.previously_destroyed,
-        ]
+        ])
     }
 
     fn block_hash(&self, number: U256) -> B256 {
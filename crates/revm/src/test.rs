@@ -10,6 +10,7 @@ use fluentbase_poseidon::poseidon_hash;
 use fluentbase_sdk::{calc_create_address, Account, ContractInput, CoreInput, EvmCallMethodInput};
 use fluentbase_types::{
     address,
+    b256,
     bytes,
     Address,
     Bytes,
@@ -17,7 +18,9 @@ use fluentbase_types::{
     SysFuncIdx,
     B256,
     KECCAK_EMPTY,
+    NATIVE_TRANSFER_KECCAK,
     POSEIDON_EMPTY,
+    STATE_MAIN,
     U256,
 };
 use lazy_static::lazy_static;
@@ -33,6 +36,7 @@ use revm_primitives::{
     ExecutionResult,
     HashMap,
     Output,
+    SpecId,
     TransactTo,
 };
 use rwasm::{
@@ -90,6 +94,7 @@ impl TestingContext {
                 source_code_hash: keccak_hash,
                 rwasm_code_size: v.code.as_ref().map(|v| v.len() as u64).unwrap_or_default(),
                 rwasm_code_hash: poseidon_hash,
+                entrypoint_mask: 0,
             };
             let mut info: AccountInfo = account.into();
             info.code = v.code.clone().map(Bytecode::new_raw);
@@ -119,6 +124,7 @@ impl TestingContext {
             source_code_hash: KECCAK_EMPTY,
             rwasm_code_size: rwasm_binary.len() as u64,
             rwasm_code_hash: poseidon_hash(&rwasm_binary).into(),
+            entrypoint_mask: 0,
         };
         let mut info: AccountInfo = account.into();
         info.code = None;
@@ -347,6 +353,93 @@ fn test_deploy_panic() {
     );
 }
 
+#[test]
+fn test_wasm_call_context() {
+    let mut ctx = TestingContext::default();
+    const DEPLOYER_ADDRESS: Address = address!("1231238908230948230948209348203984029834");
+    let contract_address = deploy_evm_tx(
+        &mut ctx,
+        DEPLOYER_ADDRESS,
+        include_bytes!("../../../examples/bin/contract_input_check_recode.wasm").into(),
+    );
+    ctx.add_balance(DEPLOYER_ADDRESS, U256::from(1e18));
+    // contract returns the context it sees, so every field must be forwarded to the callee
+    let access_list = vec![(
+        address!("00000000000000000000000000000000000000aa"),
+        vec![U256::from(1), U256::from(2)],
+    )];
+    let blob_hash = b256!("0100000000000000000000000000000000000000000000000000000000000001");
+    let mut tx_builder = TxBuilder::call(&mut ctx, DEPLOYER_ADDRESS, contract_address)
+        .input(bytes!("01020304"))
+        .value(U256::from(1000))
+        .gas_price(U256::from(2));
+    tx_builder.env.cfg.chain_id = 1337;
+    tx_builder.env.tx.nonce = Some(1);
+    tx_builder.env.tx.gas_priority_fee = Some(U256::from(1));
+    tx_builder.env.tx.access_list = access_list.clone();
+    tx_builder.env.tx.blob_hashes = vec![blob_hash];
+    tx_builder.env.tx.max_fee_per_blob_gas = Some(U256::from(1));
+    tx_builder.env.block.number = U256::from(123);
+    tx_builder.env.block.timestamp = U256::from(1_700_000_000);
+    tx_builder.env.block.coinbase = address!("0000000000000000000000000000000000c0ffee");
+    tx_builder.env.block.gas_limit = U256::from(30_000_000);
+    tx_builder.env.block.basefee = U256::from(1);
+    tx_builder.env.block.difficulty = U256::from(7);
+    let result = tx_builder.exec().unwrap();
+    assert!(result.is_success());
+    let output = result.output().unwrap_or_default();
+    let mut buffer_decoder = BufferDecoder::new(output.as_ref());
+    let mut contract_input = ContractInput::default();
+    ContractInput::decode_body(&mut buffer_decoder, 0, &mut contract_input);
+    assert_eq!(contract_input.contract_address, contract_address);
+    assert_eq!(contract_input.contract_caller, DEPLOYER_ADDRESS);
+    assert_eq!(contract_input.contract_input, bytes!("01020304"));
+    assert_eq!(contract_input.contract_is_static, false);
+    assert_eq!(contract_input.contract_value, U256::from(1000));
+    assert_eq!(contract_input.block_chain_id, 1337);
+    assert_eq!(
+        contract_input.block_coinbase,
+        address!("0000000000000000000000000000000000c0ffee")
+    );
+    assert_eq!(contract_input.block_number, 123);
+    assert_eq!(contract_input.block_timestamp, 1_700_000_000);
+    assert_eq!(contract_input.block_difficulty, 7);
+    assert_eq!(contract_input.block_gas_limit, 30_000_000);
+    assert_eq!(contract_input.block_base_fee, U256::from(1));
+    assert_eq!(contract_input.block_hardforks, vec![(SpecId::LATEST as u8, 0)]);
+    assert_eq!(contract_input.tx_caller, DEPLOYER_ADDRESS);
+    assert_eq!(contract_input.tx_nonce, 1);
+    assert_eq!(contract_input.tx_gas_price, U256::from(2));
+    assert_eq!(contract_input.tx_gas_priority_fee, Some(U256::from(1)));
+    assert_eq!(contract_input.tx_gas_limit, 10_000_000);
+    assert_eq!(contract_input.tx_access_list, access_list);
+    assert_eq!(contract_input.tx_blob_hashes, vec![blob_hash]);
+    assert_eq!(contract_input.tx_max_fee_per_blob_gas, Some(U256::from(1)));
+}
+
+#[test]
+fn test_wasm_call_with_value() {
+    let mut ctx = TestingContext::default();
+    const SENDER_ADDRESS: Address = address!("1231238908230948230948209348203984029834");
+    let contract_address = deploy_evm_tx(
+        &mut ctx,
+        SENDER_ADDRESS,
+        include_bytes!("../../../examples/bin/greeting.wasm").into(),
+    );
+    ctx.add_balance(SENDER_ADDRESS, U256::from(2e18));
+    let result = TxBuilder::call(&mut ctx, SENDER_ADDRESS, contract_address)
+        .value(U256::from(1e18))
+        .gas_price(U256::ZERO)
+        .exec()
+        .unwrap();
+    assert!(result.is_success());
+    assert_eq!(ctx.get_balance(SENDER_ADDRESS), U256::from(1e18));
+    assert_eq!(ctx.get_balance(contract_address), U256::from(1e18));
+    // value transfer is visible as a native transfer log
+    assert_eq!(result.logs().len(), 1);
+    assert_eq!(result.logs()[0].topics()[0], NATIVE_TRANSFER_KECCAK);
+}
+
 #[test]
 fn test_evm_greeting() {
     // deploy greeting EVM contract
@@ -842,6 +935,7 @@ fn test_codec_case() {
         input: Bytes::copy_from_slice(&hex::decode("").unwrap()),
         gas_limit: 9999979000,
         depth: 0,
+        state: STATE_MAIN,
     };
     let call_method_input_encoded = call_method_input.encode_to_vec(0);
     let mut buffer = BufferDecoder::new(&call_method_input_encoded);
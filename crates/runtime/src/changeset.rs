@@ -0,0 +1,139 @@
+use crate::types::TrieDbNamespace;
+use fluentbase_types::{
+    Address,
+    Bytes,
+    B256,
+    F254,
+    JZKT_ACCOUNT_BALANCE_FIELD,
+    JZKT_ACCOUNT_FIELDS_COUNT,
+    JZKT_ACCOUNT_NONCE_FIELD,
+    JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD,
+    JZKT_ACCOUNT_RWASM_CODE_SIZE_FIELD,
+    JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD,
+    JZKT_ACCOUNT_SOURCE_CODE_SIZE_FIELD,
+    JZKT_STORAGE_FIELDS_COUNT,
+    U256,
+};
+use hashbrown::HashMap;
+
+/// Values and flags of one trie key
+pub type JournalValue = (Vec<[u8; 32]>, u32);
+
+/// Changes made by one commit.
+///
+/// It's kept in the journal history to make commits reversible and passed to the commit
+/// subscribers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JournalChangeset {
+    pub prev_root: [u8; 32],
+    pub root: [u8; 32],
+    /// Previous values of all changed keys (`None` if key didn't exist)
+    pub prev_state: HashMap<[u8; 32], Option<JournalValue>>,
+    /// New values of all changed keys (`None` if key is removed)
+    pub state: HashMap<[u8; 32], Option<JournalValue>>,
+    /// Preimages added by the commit, keyed by the namespace and hash, because source and rWASM
+    /// bytecode can have the same hash
+    pub preimages: HashMap<(TrieDbNamespace, [u8; 32]), Bytes>,
+}
+
+/// Account decoded from the trie fields
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JournalAccount {
+    pub address: Address,
+    pub balance: U256,
+    pub nonce: u64,
+    pub source_code_size: u64,
+    pub source_code_hash: B256,
+    pub rwasm_code_size: u64,
+    pub rwasm_code_hash: F254,
+}
+
+impl JournalAccount {
+    /// Decodes account from the trie key and fields, returns `None` if it's not an account.
+    pub fn from_fields(key: &[u8; 32], fields: &[[u8; 32]]) -> Option<Self> {
+        // account key is an address padded to 32 bytes
+        if fields.len() != JZKT_ACCOUNT_FIELDS_COUNT as usize || key[..12] != [0u8; 12] {
+            return None;
+        }
+        let read_u64 = |field: u32| {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&fields[field as usize][..8]);
+            u64::from_le_bytes(bytes)
+        };
+        Some(Self {
+            address: Address::from_slice(&key[12..]),
+            balance: U256::from_le_slice(&fields[JZKT_ACCOUNT_BALANCE_FIELD as usize]),
+            nonce: read_u64(JZKT_ACCOUNT_NONCE_FIELD),
+            source_code_size: read_u64(JZKT_ACCOUNT_SOURCE_CODE_SIZE_FIELD),
+            source_code_hash: B256::from(fields[JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD as usize]),
+            rwasm_code_size: read_u64(JZKT_ACCOUNT_RWASM_CODE_SIZE_FIELD),
+            rwasm_code_hash: F254::from(fields[JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD as usize]),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccountChange {
+    pub prev: Option<JournalAccount>,
+    pub account: JournalAccount,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StorageChange {
+    /// Storage key, it's a hash of the address and slot
+    pub key: [u8; 32],
+    pub prev: Option<[u8; 32]>,
+    pub value: [u8; 32],
+}
+
+impl JournalChangeset {
+    /// Returns changed accounts with previous and new fields.
+    pub fn accounts(&self) -> Vec<AccountChange> {
+        self.state
+            .iter()
+            .filter_map(|(key, value)| {
+                let account = JournalAccount::from_fields(key, &value.as_ref()?.0)?;
+                let prev = self
+                    .prev_state
+                    .get(key)
+                    .cloned()
+                    .flatten()
+                    .and_then(|(fields, _)| JournalAccount::from_fields(key, &fields));
+                Some(AccountChange { prev, account })
+            })
+            .collect()
+    }
+
+    /// Returns changed storage slots with previous and new values.
+    pub fn storage(&self) -> Vec<StorageChange> {
+        self.state
+            .iter()
+            .filter_map(|(key, value)| {
+                let (fields, _) = value.as_ref()?;
+                if fields.len() != JZKT_STORAGE_FIELDS_COUNT as usize {
+                    return None;
+                }
+                let prev = self
+                    .prev_state
+                    .get(key)
+                    .cloned()
+                    .flatten()
+                    .and_then(|(fields, _)| fields.first().copied());
+                Some(StorageChange {
+                    key: *key,
+                    prev,
+                    value: fields[0],
+                })
+            })
+            .collect()
+    }
+
+    /// Returns keys removed by the commit.
+    pub fn removed(&self) -> Vec<[u8; 32]> {
+        self.state
+            .iter()
+            .filter(|(_, value)| value.is_none())
+            .map(|(key, _)| *key)
+            .collect()
+    }
+}
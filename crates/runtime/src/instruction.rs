@@ -1,4 +1,5 @@
 pub mod crypto_ecrecover;
+pub mod crypto_kzg_point_evaluation;
 pub mod crypto_keccak256;
 pub mod crypto_poseidon;
 pub mod crypto_poseidon2;
@@ -13,6 +14,8 @@ pub mod jzkt_preimage_copy;
 pub mod jzkt_preimage_size;
 pub mod jzkt_remove;
 pub mod jzkt_rollback;
+pub mod jzkt_transient_get;
+pub mod jzkt_transient_update;
 pub mod jzkt_update;
 pub mod jzkt_update_preimage;
 pub mod sys_exec_hash;
@@ -32,6 +35,7 @@ use crate::{
     impl_runtime_handler,
     instruction::{
         crypto_ecrecover::CryptoEcrecover,
+        crypto_kzg_point_evaluation::CryptoKzgPointEvaluation,
         crypto_keccak256::CryptoKeccak256,
         crypto_poseidon::CryptoPoseidon,
         crypto_poseidon2::CryptoPoseidon2,
@@ -46,6 +50,8 @@ use crate::{
         jzkt_preimage_size::JzktPreimageSize,
         jzkt_remove::JzktRemove,
         jzkt_rollback::JzktRollback,
+        jzkt_transient_get::JzktTransientGet,
+        jzkt_transient_update::JzktTransientUpdate,
         jzkt_update::JzktUpdate,
         jzkt_update_preimage::JzktUpdatePreimage,
         sys_exec_hash::SysExecHash,
@@ -81,6 +87,7 @@ impl_runtime_handler!(CryptoKeccak256, CRYPTO_KECCAK256, fn fluentbase_v1alpha::
 impl_runtime_handler!(CryptoPoseidon, CRYPTO_POSEIDON, fn fluentbase_v1alpha::_crypto_poseidon(f32s_offset: u32, f32s_len: u32, output_offset: u32) -> ());
 impl_runtime_handler!(CryptoPoseidon2, CRYPTO_POSEIDON2, fn fluentbase_v1alpha::_crypto_poseidon2(fa32_offset: u32, fb32_offset: u32, fd32_offset: u32, output_offset: u32) -> ());
 impl_runtime_handler!(CryptoEcrecover, CRYPTO_ECRECOVER, fn fluentbase_v1alpha::_crypto_ecrecover(digest32_offset: u32, sig64_offset: u32, output65_offset: u32, rec_id: u32) -> ());
+impl_runtime_handler!(CryptoKzgPointEvaluation, CRYPTO_KZG_POINT_EVALUATION, fn fluentbase_v1alpha::_crypto_kzg_point_evaluation(input192_offset: u32, output64_offset: u32) -> ());
 
 impl_runtime_handler!(SysHalt, SYS_HALT, fn fluentbase_v1alpha::_sys_halt(exit_code: i32) -> ());
 impl_runtime_handler!(SysWrite, SYS_WRITE, fn fluentbase_v1alpha::_sys_write(offset: u32, length: u32) -> ());
@@ -105,6 +112,8 @@ impl_runtime_handler!(JzktRollback, JZKT_ROLLBACK, fn fluentbase_v1alpha::_jzkt_
 impl_runtime_handler!(JzktPreimageSize, JZKT_PREIMAGE_SIZE, fn fluentbase_v1alpha::_jzkt_preimage_size(hash32_ptr: u32) -> u32);
 impl_runtime_handler!(JzktPreimageCopy, JZKT_PREIMAGE_COPY, fn fluentbase_v1alpha::_jzkt_preimage_copy(hash32_ptr: u32, preimage_ptr: u32) -> ());
 impl_runtime_handler!(JzktUpdatePreimage, JZKT_UPDATE_PREIMAGE, fn fluentbase_v1alpha::_jzkt_update_preimage(key32_ptr: u32, field: u32, preimage_ptr: u32, preimage_len: u32) -> i32);
+impl_runtime_handler!(JzktTransientGet, JZKT_TRANSIENT_GET, fn fluentbase_v1alpha::_jzkt_transient_get(key32_offset: u32, output32_offset: u32) -> ());
+impl_runtime_handler!(JzktTransientUpdate, JZKT_TRANSIENT_UPDATE, fn fluentbase_v1alpha::_jzkt_transient_update(key32_offset: u32, value32_offset: u32) -> ());
 
 impl_runtime_handler!(WasmToRwasmSize, WASM_TO_RWASM_SIZE, fn fluentbase_v1alpha::_wasm_to_rwasm_size(input_offset: u32, input_len: u32) -> i32);
 impl_runtime_handler!(WasmToRwasm, WASM_TO_RWASM, fn fluentbase_v1alpha::_wasm_to_rwasm(input_offset: u32, input_len: u32, output_offset: u32, output_len: u32) -> i32);
@@ -119,6 +128,9 @@ fn runtime_register_handlers<DB: IJournaledTrie, const IS_SOVEREIGN: bool>(
     CryptoPoseidon::register_handler(linker, store);
     CryptoPoseidon2::register_handler(linker, store);
     CryptoEcrecover::register_handler(linker, store);
+    if IS_SOVEREIGN {
+        CryptoKzgPointEvaluation::register_handler(linker, store);
+    }
     SysHalt::register_handler(linker, store);
     SysWrite::register_handler(linker, store);
     SysForwardOutput::register_handler(linker, store);
@@ -145,6 +157,8 @@ fn runtime_register_handlers<DB: IJournaledTrie, const IS_SOVEREIGN: bool>(
     if IS_SOVEREIGN {
         JzktPreimageSize::register_handler(linker, store);
         JzktUpdatePreimage::register_handler(linker, store);
+        JzktTransientGet::register_handler(linker, store);
+        JzktTransientUpdate::register_handler(linker, store);
     }
     JzktPreimageCopy::register_handler(linker, store);
     WasmToRwasmSize::register_handler(linker, store);
@@ -0,0 +1,41 @@
+use crate::RuntimeContext;
+use fluentbase_types::{ExitCode, IJournaledTrie};
+use revm_precompile::{
+    kzg_point_evaluation,
+    primitives::{Bytes, Env},
+};
+use rwasm::{core::Trap, errors::FuelError, Caller};
+
+pub struct CryptoKzgPointEvaluation;
+
+impl CryptoKzgPointEvaluation {
+    pub fn fn_handler<DB: IJournaledTrie>(
+        mut caller: Caller<'_, RuntimeContext<DB>>,
+        input192_offset: u32,
+        output64_offset: u32,
+    ) -> Result<(), Trap> {
+        match caller.consume_fuel(kzg_point_evaluation::GAS_COST) {
+            Ok(_) | Err(FuelError::FuelMeteringDisabled) => {}
+            Err(FuelError::OutOfFuel) => return Err(ExitCode::OutOfFuel.into_trap()),
+        }
+        let input = caller.read_memory(input192_offset, 192)?;
+        let output = Self::fn_impl(input).map_err(|err| err.into_trap())?;
+        caller.write_memory(output64_offset, &output)?;
+        Ok(())
+    }
+
+    /// Verifies KZG proof (EIP-4844), it checks that commitment matches the versioned hash and
+    /// that the polynomial evaluates to `y` at point `z`.
+    pub fn fn_impl(input: &[u8]) -> Result<[u8; 64], ExitCode> {
+        // fuel is charged by the handler
+        let (_, output) = kzg_point_evaluation::run(
+            &Bytes::copy_from_slice(input),
+            kzg_point_evaluation::GAS_COST,
+            &Env::default(),
+        )
+        .map_err(|_| ExitCode::PrecompileError)?;
+        let mut result = [0u8; 64];
+        result.copy_from_slice(&output);
+        Ok(result)
+    }
+}
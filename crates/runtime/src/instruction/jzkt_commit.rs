@@ -15,6 +15,9 @@ impl JzktCommit {
     }
 
     pub fn fn_impl<DB: IJournaledTrie>(ctx: &mut RuntimeContext<DB>) -> Result<[u8; 32], ExitCode> {
+        if ctx.is_static {
+            return Err(ExitCode::WriteProtection);
+        }
         let (root, _logs) = ctx.jzkt().commit()?;
         Ok(root)
     }
@@ -1,5 +1,5 @@
 use crate::RuntimeContext;
-use fluentbase_types::{Address, Bytes, IJournaledTrie, B256};
+use fluentbase_types::{Address, Bytes, ExitCode, IJournaledTrie, B256};
 use rwasm::{core::Trap, Caller};
 
 pub struct JzktEmitLog;
@@ -40,7 +40,7 @@ impl JzktEmitLog {
             })
             .collect::<Vec<_>>();
         let data = Bytes::copy_from_slice(caller.read_memory(data_ptr, data_len)?);
-        Self::fn_impl(caller.data_mut(), address, topics, data);
+        Self::fn_impl(caller.data_mut(), address, topics, data).map_err(|err| err.into_trap())?;
         Ok(())
     }
 
@@ -49,7 +49,11 @@ impl JzktEmitLog {
         address: Address,
         topics: Vec<B256>,
         data: Bytes,
-    ) {
+    ) -> Result<(), ExitCode> {
+        if ctx.is_static {
+            return Err(ExitCode::WriteProtection);
+        }
         ctx.jzkt().emit_log(address, topics.clone(), data);
+        Ok(())
     }
 }
@@ -13,13 +13,8 @@ impl JzktGet {
         committed: u32,
     ) -> Result<u32, Trap> {
         let key = caller.read_memory(key32_offset, 32)?.to_vec();
-        let is_cold = match Self::fn_impl(caller.data_mut(), &key, field, committed != 0) {
-            Some((value, is_cold)) => {
-                caller.write_memory(output32_offset, &value)?;
-                is_cold
-            }
-            None => true,
-        };
+        let (value, is_cold) = Self::fn_impl(caller.data_mut(), &key, field, committed != 0);
+        caller.write_memory(output32_offset, &value)?;
         Ok(is_cold as u32)
     }
 
@@ -28,14 +23,21 @@ impl JzktGet {
         key: &[u8],
         field: u32,
         committed: bool,
-    ) -> Option<([u8; 32], bool)> {
-        let (field_values, _flags, is_cold) = ctx.jzkt().get(key.try_into().unwrap(), committed)?;
-        let field_value = field_values.get(field as usize)?;
-        if field_value.len() < 32 {
-            return None;
-        }
+    ) -> ([u8; 32], bool) {
+        let key: &[u8; 32] = key.try_into().unwrap();
+        // committed reads are used to get original values, they don't touch the access list
+        let is_cold = if committed {
+            true
+        } else {
+            ctx.jzkt().access(key)
+        };
+        // missing keys are treated as zero values
         let mut output = [0u8; 32];
-        output.copy_from_slice(&field_value[0..32]);
-        Some((output, is_cold))
+        if let Some((field_values, _flags, _is_cold)) = ctx.jzkt().get(key, committed) {
+            if let Some(field_value) = field_values.get(field as usize) {
+                output = *field_value;
+            }
+        }
+        (output, is_cold)
     }
 }
@@ -1,5 +1,5 @@
 use crate::RuntimeContext;
-use fluentbase_types::{ExitCode, IJournaledTrie};
+use fluentbase_types::{ExitCode, IJournaledTrie, JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD};
 use rwasm::{core::Trap, Caller};
 
 pub struct JzktPreimageCopy;
@@ -11,16 +11,19 @@ impl JzktPreimageCopy {
         preimage_ptr: u32,
     ) -> Result<(), Trap> {
         let hash = caller.read_memory(hash32_ptr, 32)?.to_vec();
-        let preimage = Self::fn_impl(caller.data_mut(), &hash).map_err(|err| err.into_trap())?;
+        // contracts can read only source bytecode, rWASM is loaded by the runtime itself
+        let preimage = Self::fn_impl(caller.data_mut(), JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD, &hash)
+            .map_err(|err| err.into_trap())?;
         caller.write_memory(preimage_ptr, &preimage)?;
         Ok(())
     }
 
     pub fn fn_impl<DB: IJournaledTrie>(
         ctx: &mut RuntimeContext<DB>,
+        field: u32,
         hash: &[u8],
     ) -> Result<Vec<u8>, ExitCode> {
-        let preimage = ctx.jzkt().preimage(hash.try_into().unwrap());
+        let preimage = ctx.jzkt().preimage(field, hash.try_into().unwrap());
         Ok(preimage)
     }
 }
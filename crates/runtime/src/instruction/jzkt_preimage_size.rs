@@ -1,5 +1,5 @@
 use crate::RuntimeContext;
-use fluentbase_types::{ExitCode, IJournaledTrie};
+use fluentbase_types::{ExitCode, IJournaledTrie, JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD};
 use rwasm::{core::Trap, Caller};
 
 pub struct JzktPreimageSize;
@@ -10,14 +10,17 @@ impl JzktPreimageSize {
         hash32_offset: u32,
     ) -> Result<u32, Trap> {
         let hash = caller.read_memory(hash32_offset, 32)?.to_vec();
-        Self::fn_impl(caller.data_mut(), &hash).map_err(|err| err.into_trap())
+        // contracts can read only source bytecode, rWASM is loaded by the runtime itself
+        Self::fn_impl(caller.data_mut(), JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD, &hash)
+            .map_err(|err| err.into_trap())
     }
 
     pub fn fn_impl<DB: IJournaledTrie>(
         ctx: &mut RuntimeContext<DB>,
+        field: u32,
         hash: &[u8],
     ) -> Result<u32, ExitCode> {
-        let preimage_size = ctx.jzkt().preimage_size(hash.try_into().unwrap());
+        let preimage_size = ctx.jzkt().preimage_size(field, hash.try_into().unwrap());
         Ok(preimage_size)
     }
 }
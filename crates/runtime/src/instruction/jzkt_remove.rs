@@ -18,6 +18,9 @@ impl JzktRemove {
         ctx: &mut RuntimeContext<DB>,
         key: &[u8],
     ) -> Result<(), ExitCode> {
+        if ctx.is_static {
+            return Err(ExitCode::WriteProtection);
+        }
         ctx.jzkt().remove(key.try_into().unwrap());
         Ok(())
     }
@@ -1,5 +1,5 @@
 use crate::RuntimeContext;
-use fluentbase_types::{IJournaledTrie, JournalCheckpoint};
+use fluentbase_types::{ExitCode, IJournaledTrie, JournalCheckpoint};
 use rwasm::{core::Trap, Caller};
 
 pub struct JzktRollback;
@@ -9,14 +9,28 @@ impl JzktRollback {
         mut caller: Caller<'_, RuntimeContext<DB>>,
         checkpoint: u64,
     ) -> Result<(), Trap> {
-        Self::fn_impl(caller.data_mut(), JournalCheckpoint::from_u64(checkpoint));
+        Self::fn_impl(caller.data_mut(), JournalCheckpoint::from_u64(checkpoint))
+            .map_err(|err| err.into_trap())?;
         Ok(())
     }
 
     pub fn fn_impl<DB: IJournaledTrie>(
         ctx: &mut RuntimeContext<DB>,
         checkpoint: JournalCheckpoint,
-    ) {
+    ) -> Result<(), ExitCode> {
+        // static frame can revert only its own changes (warm accesses), but not the parent's
+        if ctx.is_static {
+            let static_checkpoint = ctx
+                .static_checkpoint
+                .map(JournalCheckpoint::from_u64)
+                .ok_or(ExitCode::WriteProtection)?;
+            if checkpoint.state() < static_checkpoint.state()
+                || checkpoint.logs() < static_checkpoint.logs()
+            {
+                return Err(ExitCode::WriteProtection);
+            }
+        }
         ctx.jzkt().rollback(checkpoint);
+        Ok(())
     }
 }
@@ -0,0 +1,25 @@
+use crate::RuntimeContext;
+use fluentbase_types::IJournaledTrie;
+use rwasm::{core::Trap, Caller};
+
+pub struct JzktTransientGet;
+
+impl JzktTransientGet {
+    pub fn fn_handler<DB: IJournaledTrie>(
+        mut caller: Caller<'_, RuntimeContext<DB>>,
+        key32_offset: u32,
+        output32_offset: u32,
+    ) -> Result<(), Trap> {
+        let key = caller.read_memory(key32_offset, 32)?.to_vec();
+        let value = Self::fn_impl(caller.data_mut(), &key);
+        caller.write_memory(output32_offset, &value)?;
+        Ok(())
+    }
+
+    pub fn fn_impl<DB: IJournaledTrie>(ctx: &mut RuntimeContext<DB>, key: &[u8]) -> [u8; 32] {
+        // missing transient slots are always zero
+        ctx.jzkt()
+            .transient_get(key.try_into().unwrap())
+            .unwrap_or_default()
+    }
+}
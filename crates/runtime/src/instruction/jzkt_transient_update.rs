@@ -0,0 +1,31 @@
+use crate::RuntimeContext;
+use fluentbase_types::{ExitCode, IJournaledTrie};
+use rwasm::{core::Trap, Caller};
+
+pub struct JzktTransientUpdate;
+
+impl JzktTransientUpdate {
+    pub fn fn_handler<DB: IJournaledTrie>(
+        mut caller: Caller<'_, RuntimeContext<DB>>,
+        key32_offset: u32,
+        value32_offset: u32,
+    ) -> Result<(), Trap> {
+        let key = caller.read_memory(key32_offset, 32)?.to_vec();
+        let value = caller.read_memory(value32_offset, 32)?.to_vec();
+        Self::fn_impl(caller.data_mut(), &key, &value).map_err(|err| err.into_trap())?;
+        Ok(())
+    }
+
+    pub fn fn_impl<DB: IJournaledTrie>(
+        ctx: &mut RuntimeContext<DB>,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<(), ExitCode> {
+        if ctx.is_static {
+            return Err(ExitCode::WriteProtection);
+        }
+        ctx.jzkt()
+            .transient_update(key.try_into().unwrap(), value.try_into().unwrap());
+        Ok(())
+    }
+}
@@ -32,6 +32,9 @@ impl JzktUpdate {
         value_flags: u32,
         vals: Vec<[u8; 32]>,
     ) -> Result<(), ExitCode> {
+        if ctx.is_static {
+            return Err(ExitCode::WriteProtection);
+        }
         ctx.jzkt()
             .update(key.try_into().unwrap(), &vals, value_flags);
         Ok(())
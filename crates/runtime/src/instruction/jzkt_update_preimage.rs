@@ -25,6 +25,9 @@ impl JzktUpdatePreimage {
         field: u32,
         preimage: &[u8],
     ) -> Result<bool, ExitCode> {
+        if ctx.is_static {
+            return Err(ExitCode::WriteProtection);
+        }
         let res = ctx
             .jzkt()
             .update_preimage(key.try_into().unwrap(), field, preimage);
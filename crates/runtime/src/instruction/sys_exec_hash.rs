@@ -1,6 +1,6 @@
 use crate::{ExecutionResult, Runtime, RuntimeContext};
 use byteorder::{ByteOrder, LittleEndian};
-use fluentbase_types::{ExitCode, IJournaledTrie};
+use fluentbase_types::{ExitCode, IJournaledTrie, STATE_STATIC_FLAG};
 use rwasm::{
     core::{HostError, Trap},
     Caller,
@@ -112,6 +112,10 @@ impl SysExecHash {
             return Err(ExitCode::CallDepthOverflow.into_i32());
         }
 
+        // static mode is inherited by all nested calls
+        let is_static = ctx.is_static || state & STATE_STATIC_FLAG != 0;
+        let state = state & !STATE_STATIC_FLAG;
+
         // create new runtime instance with the context
         let ctx2 = RuntimeContext::new_with_hash(bytecode_hash32.into())
             .with_input(input)
@@ -120,7 +124,9 @@ impl SysExecHash {
             .with_fuel_limit(fuel_limit)
             .with_jzkt(jzkt)
             .with_state(state)
-            .with_depth(ctx.depth + 1);
+            .with_depth(ctx.depth + 1)
+            .with_is_static(is_static)
+            .with_static_checkpoint(ctx.static_checkpoint);
         let mut runtime = Runtime::new(ctx2);
         let execution_result = runtime
             .call()
@@ -1,15 +1,13 @@
 use crate::RuntimeContext;
 use fluentbase_types::{
     create_sovereign_import_linker,
+    create_state_router_config,
     ExitCode,
     IJournaledTrie,
-    SysFuncIdx::SYS_STATE,
-    STATE_DEPLOY,
-    STATE_MAIN,
 };
 use rwasm::{
     core::Trap,
-    engine::{bytecode::Instruction, RwasmConfig, StateRouterConfig},
+    engine::RwasmConfig,
     rwasm::{BinaryFormat, BinaryFormatWriter, RwasmModule},
     Caller,
 };
@@ -20,13 +18,7 @@ pub struct WasmToRwasm;
 pub fn wasm2rwasm(wasm_binary: &[u8]) -> Result<Vec<u8>, ExitCode> {
     let mut config = RwasmModule::default_config(None);
     config.rwasm_config(RwasmConfig {
-        state_router: Some(StateRouterConfig {
-            states: Box::new([
-                ("deploy".to_string(), STATE_DEPLOY),
-                ("main".to_string(), STATE_MAIN),
-            ]),
-            opcode: Instruction::Call(SYS_STATE.into()),
-        }),
+        state_router: Some(create_state_router_config()),
         entrypoint_name: None,
         import_linker: Some(create_sovereign_import_linker()),
         wrap_import_functions: true,
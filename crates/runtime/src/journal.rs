@@ -1,4 +1,9 @@
-use crate::{types::InMemoryTrieDb, zktrie::ZkTrieStateDb, TrieStorage};
+use crate::{
+    changeset::JournalChangeset,
+    types::{InMemoryTrieDb, TrieDbNamespace},
+    zktrie::ZkTrieStateDb,
+    TrieStorage,
+};
 use core::mem::take;
 use fluentbase_poseidon::{hash_with_domain, Poseidon};
 use fluentbase_types::{
@@ -12,8 +17,11 @@ use fluentbase_types::{
     B256,
 };
 use halo2curves::bn256::Fr;
-use hashbrown::HashMap;
-use std::sync::{Arc, RwLock};
+use hashbrown::{HashMap, HashSet};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex, RwLock},
+};
 
 macro_rules! bytes32 {
     ($val:literal) => {
@@ -30,14 +38,42 @@ macro_rules! bytes32 {
     }};
 }
 
+/// Default number of last commits that can be reverted
+pub const JOURNAL_HISTORY_LIMIT: usize = 64;
+
+/// Base of the copy-on-write fork
+struct JournalFork {
+    /// Parent's committed root the fork was created at
+    base_root: [u8; 32],
+    /// Parent's journal length and generation the fork was created at
+    base_journal: Option<(usize, usize)>,
+    /// Parent's uncommitted values visible to the fork
+    pending: HashMap<[u8; 32], Option<(Vec<[u8; 32]>, u32)>>,
+    /// Keys read by the fork from the parent's view
+    reads: Mutex<HashSet<[u8; 32]>>,
+}
+
+/// Callback that is notified about every commit
+pub type JournalSubscriber = Box<dyn Fn(&JournalChangeset) + Send + Sync>;
+
 struct JournalTrieInner<DB: TrieStorage> {
     storage: DB,
     state: HashMap<[u8; 32], usize>,
-    preimages: HashMap<[u8; 32], Vec<u8>>,
+    /// Transient (EIP-1153) slots, they live in the journal until the end of transaction
+    transient: HashMap<[u8; 32], usize>,
+    /// Keys accessed in the current transaction (EIP-2929)
+    accessed: HashSet<[u8; 32]>,
+    preimages: HashMap<(TrieDbNamespace, [u8; 32]), Vec<u8>>,
     logs: Vec<JournalLog>,
     journal: Vec<JournalEvent>,
     root: [u8; 32],
     committed: usize,
+    read_only: bool,
+    history: VecDeque<JournalChangeset>,
+    history_limit: usize,
+    fork: Option<JournalFork>,
+    /// Incremented every time uncommitted changes are discarded
+    generation: usize,
 }
 
 impl<DB: TrieStorage> JournalTrieInner<DB> {
@@ -57,7 +93,24 @@ impl<DB: TrieStorage> JournalTrieInner<DB> {
         }
     }
 
+    fn warm(&mut self, key: &[u8; 32]) -> bool {
+        if !self.accessed.insert(*key) {
+            return false;
+        }
+        // warming must be reverted with the frame, so it goes into the journal as well
+        self.journal.push(JournalEvent::KeyWarmed { key: *key });
+        true
+    }
+
     fn get_committed(&self, key: &[u8; 32]) -> Option<(Vec<[u8; 32]>, u32, bool)> {
+        if let Some(fork) = &self.fork {
+            fork.reads.lock().unwrap().insert(*key);
+            if let Some(value) = fork.pending.get(key) {
+                return value
+                    .clone()
+                    .map(|(values, flags)| (values, flags, true));
+            }
+        }
         self.storage
             .get(key)
             .map(|(values, flags)| (values, flags, true))
@@ -83,6 +136,23 @@ impl<DB: TrieStorage> JournalTrieInner<DB> {
         self.state.insert(*key, pos);
     }
 
+    fn transient_get(&self, key: &[u8; 32]) -> Option<[u8; 32]> {
+        let index = self.transient.get(key)?;
+        self.journal[*index]
+            .preimage()
+            .and_then(|(values, _)| values.first().copied())
+    }
+
+    fn transient_update(&mut self, key: &[u8; 32], value: &[u8; 32]) {
+        let pos = self.journal.len();
+        self.journal.push(JournalEvent::TransientChanged {
+            key: *key,
+            value: *value,
+            prev_state: self.transient.get(key).copied(),
+        });
+        self.transient.insert(*key, pos);
+    }
+
     fn compute_root(&self) -> [u8; 32] {
         self.storage.compute_root()
     }
@@ -99,35 +169,92 @@ impl<DB: TrieStorage> JournalTrieInner<DB> {
         return &self.journal;
     }
 
-    fn commit(&mut self) -> Result<([u8; 32], Vec<JournalLog>), ExitCode> {
+    fn commit(&mut self) -> Result<(JournalChangeset, Vec<JournalLog>), ExitCode> {
+        if self.read_only {
+            return Err(ExitCode::WriteProtection);
+        }
+        let mut changeset = JournalChangeset {
+            prev_root: self.root,
+            root: self.root,
+            prev_state: HashMap::new(),
+            state: HashMap::new(),
+            preimages: HashMap::new(),
+        };
         for (key, value) in self
             .journal
             .iter()
             .skip(self.committed)
+            .filter(|v| v.is_trie_change())
             .map(|v| (*v.key(), v.preimage()))
             .collect::<HashMap<_, _>>()
             .into_iter()
         {
-            match value {
+            changeset.prev_state.insert(key, self.storage.get(&key[..]));
+            match &value {
                 Some((value, flags)) => {
-                    self.storage.update(&key[..], flags, &value)?;
+                    self.storage.update(&key[..], *flags, value)?;
                 }
                 None => {
                     self.storage.remove(&key[..])?;
                 }
             }
+            changeset.state.insert(key, value);
         }
-        for (hash, preimage) in self.preimages.iter() {
+        for ((namespace, hash), preimage) in self.preimages.iter() {
+            let preimage = Bytes::from(preimage.clone());
             self.storage
-                .update_preimage(hash, Bytes::from(preimage.clone()));
+                .update_preimage(*namespace, hash, preimage.clone())?;
+            changeset.preimages.insert((*namespace, *hash), preimage);
         }
         self.journal.clear();
+        self.generation += 1;
         self.preimages.clear();
         self.state.clear();
+        // transaction is over, so transient storage and access lists must be discarded
+        self.transient.clear();
+        self.accessed.clear();
         let logs = take(&mut self.logs);
         self.committed = 0;
         self.root = self.storage.compute_root();
-        Ok((self.root, logs))
+        changeset.root = self.root;
+        if self.history_limit > 0 {
+            if self.history.len() == self.history_limit {
+                self.history.pop_front();
+            }
+            self.history.push_back(changeset.clone());
+        }
+        Ok((changeset, logs))
+    }
+
+    fn revert_to_root(&mut self, root: &[u8; 32]) -> Result<Vec<JournalChangeset>, ExitCode> {
+        if self.read_only {
+            return Err(ExitCode::WriteProtection);
+        }
+        let position = if self.root == *root {
+            self.history.len()
+        } else {
+            self.history
+                .iter()
+                .rposition(|changeset| changeset.prev_root == *root)
+                .ok_or(ExitCode::PersistentStorageError)?
+        };
+        // nodes of the old root are still in the backend, so it's enough to reopen the trie
+        self.storage = self
+            .storage
+            .snapshot(root)
+            .ok_or(ExitCode::PersistentStorageError)?;
+        self.journal.clear();
+        self.generation += 1;
+        self.preimages.clear();
+        self.state.clear();
+        self.transient.clear();
+        self.accessed.clear();
+        self.logs.clear();
+        self.committed = 0;
+        self.root = *root;
+        let mut reverted = self.history.split_off(position).into_iter().collect::<Vec<_>>();
+        reverted.reverse();
+        Ok(reverted)
     }
 
     fn rollback(&mut self, checkpoint: JournalCheckpoint) {
@@ -144,18 +271,103 @@ impl<DB: TrieStorage> JournalTrieInner<DB> {
             .iter()
             .rev()
             .take(self.journal.len() - checkpoint.state())
-            .for_each(|v| match v.prev_state() {
-                Some(prev_state) => {
-                    self.state.insert(*v.key(), prev_state);
+            .for_each(|v| {
+                if let JournalEvent::KeyWarmed { key } = v {
+                    self.accessed.remove(key);
+                    return;
                 }
-                None => {
-                    self.state.remove(v.key());
+                let state = if v.is_transient() {
+                    &mut self.transient
+                } else {
+                    &mut self.state
+                };
+                match v.prev_state() {
+                    Some(prev_state) => {
+                        state.insert(*v.key(), prev_state);
+                    }
+                    None => {
+                        state.remove(v.key());
+                    }
                 }
             });
+        if checkpoint.state() < self.journal.len() {
+            self.generation += 1;
+        }
         self.journal.truncate(checkpoint.state());
         self.logs.truncate(checkpoint.logs());
     }
 
+    /// Returns keys changed since the fork was created, including uncommitted changes.
+    ///
+    /// Returns `None` if the fork root is too old and not in the history anymore.
+    fn changed_keys_since(&self, fork: &JournalFork) -> Option<HashSet<[u8; 32]>> {
+        match fork.base_journal {
+            // journal was only extended, so it's enough to check new events
+            Some((len, generation))
+                if self.root == fork.base_root
+                    && self.generation == generation
+                    && len <= self.journal.len() =>
+            {
+                return Some(
+                    self.journal[len..]
+                        .iter()
+                        .filter(|v| v.is_trie_change())
+                        .map(|v| *v.key())
+                        .collect(),
+                );
+            }
+            _ => {}
+        }
+        let mut result = self.state.keys().copied().collect::<HashSet<_>>();
+        // pending values seen by the fork might be discarded already
+        result.extend(fork.pending.keys().copied());
+        if self.root != fork.base_root {
+            let position = self
+                .history
+                .iter()
+                .rposition(|changeset| changeset.prev_root == fork.base_root)?;
+            self.history
+                .iter()
+                .skip(position)
+                .for_each(|changeset| result.extend(changeset.state.keys().copied()));
+        }
+        Some(result)
+    }
+
+    fn merge(&mut self, fork: &mut JournalTrieInner<DB>) -> Result<(), JournalMergeError> {
+        let Some(base) = fork.fork.as_ref() else {
+            return Err(JournalMergeError::NotFork);
+        };
+        let mut touched = base.reads.lock().unwrap().clone();
+        touched.extend(fork.state.keys().copied());
+        match self.changed_keys_since(base) {
+            Some(changed) => {
+                let conflicts = touched.intersection(&changed).copied().collect::<Vec<_>>();
+                if !conflicts.is_empty() {
+                    return Err(JournalMergeError::Conflicts(conflicts));
+                }
+            }
+            None => return Err(JournalMergeError::Conflicts(touched.into_iter().collect())),
+        }
+        for (key, index) in fork.state.iter() {
+            match fork.journal[*index].preimage() {
+                Some((values, flags)) => self.update(key, &values, flags),
+                None => self.remove(key),
+            }
+        }
+        for index in fork.transient.values() {
+            if let JournalEvent::TransientChanged { key, value, .. } = &fork.journal[*index] {
+                self.transient_update(key, value);
+            }
+        }
+        for key in fork.accessed.iter() {
+            self.warm(key);
+        }
+        self.preimages.extend(take(&mut fork.preimages));
+        self.logs.append(&mut fork.logs);
+        Ok(())
+    }
+
     fn update_preimage(&mut self, key: &[u8; 32], field: u32, preimage: &[u8]) -> bool {
         // find and decode value and hash
         let value_hash = match self
@@ -168,40 +380,59 @@ impl<DB: TrieStorage> JournalTrieInner<DB> {
         // value hash stored inside trie must be equal to the provided value hash
         // TODO(dmitry123): "we can't do this check here because hash can also be keccak256"
         // write new preimage value into database
-        self.preimages.insert(value_hash, preimage.to_vec());
+        self.preimages.insert(
+            (TrieDbNamespace::from_account_field(field), value_hash),
+            preimage.to_vec(),
+        );
         true
     }
 
-    fn preimage(&mut self, hash: &[u8; 32]) -> Vec<u8> {
+    fn preimage(&mut self, field: u32, hash: &[u8; 32]) -> Vec<u8> {
+        let namespace = TrieDbNamespace::from_account_field(field);
         // maybe its just changed preimage and we have it in the state
-        if let Some(preimage) = self.preimages.get(hash) {
+        if let Some(preimage) = self.preimages.get(&(namespace, *hash)) {
             return preimage.clone();
         }
         // get preimage from database
         let preimage = self
             .storage
-            .get_preimage(hash)
+            .get_preimage(namespace, hash)
             .map(|v| v.to_vec())
             .unwrap_or_default();
         preimage
     }
 
-    fn preimage_size(&mut self, hash: &[u8; 32]) -> u32 {
-        if let Some(preimage) = self.preimages.get(hash) {
+    fn preimage_size(&mut self, field: u32, hash: &[u8; 32]) -> u32 {
+        let namespace = TrieDbNamespace::from_account_field(field);
+        if let Some(preimage) = self.preimages.get(&(namespace, *hash)) {
             return preimage.len() as u32;
         }
-        self.storage.preimage_size(hash)
+        self.storage.preimage_size(namespace, hash)
     }
 }
 
+/// Reason why fork can't be merged into the journal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JournalMergeError {
+    /// Merged journal isn't a fork
+    NotFork,
+    /// Journal can't be merged into itself
+    SelfMerge,
+    /// Keys read or written by the fork that were changed by the journal after the fork was
+    /// created
+    Conflicts(Vec<[u8; 32]>),
+}
+
 pub struct JournaledTrie<DB: TrieStorage> {
     inner: Arc<RwLock<JournalTrieInner<DB>>>,
+    subscribers: Arc<RwLock<Vec<JournalSubscriber>>>,
 }
 
 impl<DB: TrieStorage> Clone for JournaledTrie<DB> {
     fn clone(&self) -> Self {
         Self {
             inner: self.inner.clone(),
+            subscribers: self.subscribers.clone(),
         }
     }
 }
@@ -216,18 +447,150 @@ impl<DB: TrieStorage> JournaledTrie<DB> {
     const DOMAIN: Fr = Fr::zero();
 
     pub fn new(storage: DB) -> Self {
+        Self::new_with_mode(storage, false)
+    }
+
+    fn new_with_mode(storage: DB, read_only: bool) -> Self {
         let root = storage.compute_root();
         Self {
             inner: Arc::new(RwLock::new(JournalTrieInner {
                 storage,
                 state: HashMap::new(),
+                transient: HashMap::new(),
+                accessed: HashSet::new(),
                 preimages: HashMap::new(),
                 logs: Vec::new(),
                 journal: Vec::new(),
                 root,
                 committed: 0,
+                read_only,
+                history: VecDeque::new(),
+                history_limit: JOURNAL_HISTORY_LIMIT,
+                fork: None,
+                generation: 0,
             })),
+            subscribers: Arc::new(RwLock::new(Vec::new())),
+        }
+    }
+
+    /// Opens read-only snapshot of the state at one of the previously committed roots.
+    ///
+    /// Snapshot shares node backend with the head, so it's cheap to create. Changes made inside
+    /// the snapshot are kept in its own journal and can't be committed, that makes it suitable for
+    /// `eth_call` against historical blocks. Returns `None` if the root is unknown.
+    pub fn at_root(&self, root: &[u8; 32]) -> Option<Self> {
+        let storage = self.inner.read().unwrap().storage.snapshot(root)?;
+        Some(Self::new_with_mode(storage, true))
+    }
+
+    /// Creates copy-on-write fork for speculative execution (simulation, gas estimation etc).
+    ///
+    /// Fork has its own journal on top of the current committed state, so its changes are not
+    /// visible to the parent. Fork can't be committed, it should be either dropped or merged back
+    /// using [`JournaledTrie::merge`].
+    pub fn fork(&self) -> Self {
+        self.fork_with_pending(false)
+    }
+
+    /// Creates copy-on-write fork that also sees uncommitted changes of the journal.
+    pub fn fork_head(&self) -> Self {
+        self.fork_with_pending(true)
+    }
+
+    fn fork_with_pending(&self, with_pending: bool) -> Self {
+        let inner = self.inner.read().unwrap();
+        let storage = inner
+            .storage
+            .snapshot(&inner.root)
+            .expect("committed root must exist");
+        let (base_journal, pending) = if with_pending {
+            let pending = inner
+                .state
+                .iter()
+                .map(|(key, index)| (*key, inner.journal[*index].preimage()))
+                .collect();
+            (Some((inner.journal.len(), inner.generation)), pending)
+        } else {
+            (None, HashMap::new())
+        };
+        let fork = Self::new_with_mode(storage, true);
+        fork.inner.write().unwrap().fork = Some(JournalFork {
+            base_root: inner.root,
+            base_journal,
+            pending,
+            reads: Mutex::new(HashSet::new()),
+        });
+        fork
+    }
+
+    /// Returns keys read and written by the fork.
+    pub fn fork_access_sets(&self) -> (HashSet<[u8; 32]>, HashSet<[u8; 32]>) {
+        let inner = self.inner.read().unwrap();
+        let reads = inner
+            .fork
+            .as_ref()
+            .map(|fork| fork.reads.lock().unwrap().clone())
+            .unwrap_or_default();
+        (reads, inner.state.keys().copied().collect())
+    }
+
+    /// Merges changes of the fork into the journal as uncommitted changes.
+    ///
+    /// Merge fails if the journal changed any key that the fork has read or written after the
+    /// fork was created, conflicting keys are returned in this case.
+    pub fn merge(&self, fork: Self) -> Result<(), JournalMergeError> {
+        if Arc::ptr_eq(&self.inner, &fork.inner) {
+            return Err(JournalMergeError::SelfMerge);
+        }
+        let mut fork = fork.inner.write().unwrap();
+        self.inner.write().unwrap().merge(&mut fork)
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.inner.read().unwrap().read_only
+    }
+
+    /// Sets how many last commits are kept as reversible changesets.
+    pub fn with_history_limit(self, history_limit: usize) -> Self {
+        {
+            let mut inner = self.inner.write().unwrap();
+            inner.history_limit = history_limit;
+            while inner.history.len() > history_limit {
+                inner.history.pop_front();
+            }
+        }
+        self
+    }
+
+    /// Registers callback that receives changeset of every successful commit.
+    pub fn subscribe<F: Fn(&JournalChangeset) + Send + Sync + 'static>(&self, subscriber: F) {
+        self.subscribers
+            .write()
+            .unwrap()
+            .push(Box::new(subscriber));
+    }
+
+    /// Commits all changes and returns structured changeset along with the emitted logs.
+    pub fn commit_changeset(&self) -> Result<(JournalChangeset, Vec<JournalLog>), ExitCode> {
+        // release state lock before notifying, subscribers might want to read the state
+        let (changeset, logs) = self.inner.write().unwrap().commit()?;
+        for subscriber in self.subscribers.read().unwrap().iter() {
+            subscriber(&changeset);
         }
+        Ok((changeset, logs))
+    }
+
+    /// Returns changesets of the last commits, the oldest goes first.
+    pub fn history(&self) -> Vec<JournalChangeset> {
+        self.inner.read().unwrap().history.iter().cloned().collect()
+    }
+
+    /// Reverts head to one of the recently committed roots (block-level reorg).
+    ///
+    /// All uncommitted changes are discarded. Returns reverted changesets starting from the latest
+    /// one, or [`ExitCode::PersistentStorageError`] if the root is not in the history.
+    pub fn revert_to_root(&self, root: &[u8; 32]) -> Result<Vec<JournalChangeset>, ExitCode> {
+        self.inner.write().unwrap().revert_to_root(root)
     }
 
     pub fn message_hash(val: &[u8]) -> Fr {
@@ -290,7 +653,8 @@ impl<DB: TrieStorage> IJournaledTrie for JournaledTrie<DB> {
     }
 
     fn commit(&self) -> Result<([u8; 32], Vec<JournalLog>), ExitCode> {
-        self.inner.write().unwrap().commit()
+        self.commit_changeset()
+            .map(|(changeset, logs)| (changeset.root, logs))
     }
 
     fn rollback(&self, checkpoint: JournalCheckpoint) {
@@ -304,29 +668,53 @@ impl<DB: TrieStorage> IJournaledTrie for JournaledTrie<DB> {
             .update_preimage(key, field, preimage)
     }
 
-    fn preimage(&self, hash: &[u8; 32]) -> Vec<u8> {
-        self.inner.write().unwrap().preimage(hash)
+    fn preimage(&self, field: u32, hash: &[u8; 32]) -> Vec<u8> {
+        self.inner.write().unwrap().preimage(field, hash)
     }
 
-    fn preimage_size(&self, hash: &[u8; 32]) -> u32 {
-        self.inner.write().unwrap().preimage_size(hash)
+    fn preimage_size(&self, field: u32, hash: &[u8; 32]) -> u32 {
+        self.inner.write().unwrap().preimage_size(field, hash)
     }
 
     fn journal(&self) -> Vec<JournalEvent> {
         self.inner.write().unwrap().journal.clone()
     }
+
+    fn access(&self, key: &[u8; 32]) -> bool {
+        self.inner.write().unwrap().warm(key)
+    }
+
+    fn transient_get(&self, key: &[u8; 32]) -> Option<[u8; 32]> {
+        self.inner.read().unwrap().transient_get(key)
+    }
+
+    fn transient_update(&self, key: &[u8; 32], value: &[u8; 32]) {
+        self.inner.write().unwrap().transient_update(key, value)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        journal::{IJournaledTrie, JournaledTrie},
-        types::InMemoryTrieDb,
+        journal::{IJournaledTrie, JournalMergeError, JournaledTrie},
+        types::{InMemoryTrieDb, TrieDb, TrieDbNamespace},
         zktrie::ZkTrieStateDb,
         TrieStorage,
     };
     use fluentbase_poseidon::poseidon_hash;
-    use fluentbase_types::JournalCheckpoint;
+    use fluentbase_types::{
+        Address,
+        Bytes,
+        ExitCode,
+        JournalCheckpoint,
+        JZKT_ACCOUNT_BALANCE_FIELD,
+        JZKT_ACCOUNT_FIELDS_COUNT,
+        JZKT_ACCOUNT_NONCE_FIELD,
+        JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD,
+        JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD,
+        U256,
+    };
+    use std::sync::{Arc, Mutex};
 
     fn calc_trie_root(values: Vec<([u8; 32], Vec<[u8; 32]>, u32)>) -> [u8; 32] {
         let db = InMemoryTrieDb::default();
@@ -401,9 +789,256 @@ mod tests {
         account1_fields[2] = code1_hash;
         journal.update(&address1, &account1_fields.to_vec(), 12);
         assert!(journal.update_preimage(&address1, 2, &code1));
-        assert_eq!(code1, journal.preimage(&code1_hash));
+        assert_eq!(code1, journal.preimage(2, &code1_hash));
+        journal.commit().unwrap();
+        assert_eq!(code1, journal.preimage(2, &code1_hash));
+    }
+
+    #[test]
+    fn test_node_and_preimage_namespaces_dont_collide() {
+        let mut db = InMemoryTrieDb::default();
+        let key = bytes32!("key1");
+        db.update_node(&key, Bytes::from_static(&[0x01]));
+        assert_eq!(
+            db.update_preimage(TrieDbNamespace::Rwasm, &key, Bytes::from_static(&[0x02])),
+            Ok(())
+        );
+        assert_eq!(db.get_node(&key), Some(Bytes::from_static(&[0x01])));
+        // preimages can't replace nodes or be read from the node namespace
+        assert_eq!(
+            db.update_preimage(TrieDbNamespace::Node, &key, Bytes::from_static(&[0x03])),
+            Err(ExitCode::PersistentStorageError)
+        );
+        assert_eq!(db.get_preimage(TrieDbNamespace::Node, &key), None);
+        assert_eq!(db.get_node(&key), Some(Bytes::from_static(&[0x01])));
+        assert_eq!(
+            db.get_preimage(TrieDbNamespace::Rwasm, &key),
+            Some(Bytes::from_static(&[0x02]))
+        );
+        assert_eq!(db.get(TrieDbNamespace::Bytecode, &key), None);
+    }
+
+    #[test]
+    fn test_preimages_are_looked_up_by_namespace() {
+        let journal = JournaledTrie::new(ZkTrieStateDb::new_empty(InMemoryTrieDb::default()));
+        let address1 = bytes32!("address1");
+        // the same hash is used for both source and rWASM code, so preimages collide by key
+        let hash = bytes32!("hash1");
+        let mut account1_fields = [[0u8; 32]; JZKT_ACCOUNT_FIELDS_COUNT as usize];
+        account1_fields[JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD as usize] = hash;
+        account1_fields[JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD as usize] = hash;
+        journal.update(&address1, &account1_fields.to_vec(), 0);
+        assert!(journal.update_preimage(&address1, JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD, &[1]));
+        assert!(journal.update_preimage(&address1, JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD, &[2, 2]));
+        for _ in 0..2 {
+            assert_eq!(journal.preimage(JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD, &hash), vec![1]);
+            assert_eq!(journal.preimage(JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD, &hash), vec![2, 2]);
+            assert_eq!(journal.preimage_size(JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD, &hash), 2);
+            assert_eq!(journal.preimage_size(JZKT_ACCOUNT_NONCE_FIELD, &hash), 0);
+            // the same lookups must work after preimages are moved into the database
+            journal.commit().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_read_state_at_past_root() {
+        let journal = JournaledTrie::new(ZkTrieStateDb::new_empty(InMemoryTrieDb::default()));
+        let key1 = bytes32!("key1");
+        journal.update(&key1, &vec![bytes32!("val1")], 0);
+        let (root1, _) = journal.commit().unwrap();
+        journal.update(&key1, &vec![bytes32!("val2")], 0);
+        journal.update(&bytes32!("key2"), &vec![bytes32!("val3")], 0);
+        let (root2, _) = journal.commit().unwrap();
+        // old root still sees old values while head moves on
+        let snapshot = journal.at_root(&root1).unwrap();
+        assert!(snapshot.is_read_only());
+        assert_eq!(snapshot.compute_root(), root1);
+        assert_eq!(
+            snapshot.get(&key1, false).map(|v| v.0),
+            Some(vec![bytes32!("val1")])
+        );
+        assert_eq!(snapshot.get(&bytes32!("key2"), false), None);
+        assert_eq!(
+            journal.get(&key1, false).map(|v| v.0),
+            Some(vec![bytes32!("val2")])
+        );
+        // writes inside snapshot can't be committed and don't affect head
+        snapshot.update(&key1, &vec![bytes32!("val4")], 0);
+        assert_eq!(snapshot.commit().err(), Some(ExitCode::WriteProtection));
+        assert_eq!(journal.compute_root(), root2);
+        assert!(journal.at_root(&bytes32!("unknown")).is_none());
+    }
+
+    #[test]
+    fn test_revert_to_committed_root() {
+        let journal = JournaledTrie::new(ZkTrieStateDb::new_empty(InMemoryTrieDb::default()))
+            .with_history_limit(2);
+        let key1 = bytes32!("key1");
+        let key2 = bytes32!("key2");
+        let root0 = journal.compute_root();
+        journal.update(&key1, &vec![bytes32!("val1")], 0);
+        let (root1, _) = journal.commit().unwrap();
+        journal.update(&key1, &vec![bytes32!("val2")], 0);
+        journal.update(&key2, &vec![bytes32!("val3")], 0);
+        let (root2, _) = journal.commit().unwrap();
+        journal.update(&key2, &vec![bytes32!("val4")], 0);
+        let (root3, _) = journal.commit().unwrap();
+        // only two last commits are reversible
+        assert_eq!(journal.history().len(), 2);
+        assert_eq!(
+            journal.revert_to_root(&root0).err(),
+            Some(ExitCode::PersistentStorageError)
+        );
+        // uncommitted changes are discarded as well
+        journal.update(&key1, &vec![bytes32!("val5")], 0);
+        let reverted = journal.revert_to_root(&root1).unwrap();
+        assert_eq!(reverted.len(), 2);
+        assert_eq!(reverted[0].root, root3);
+        assert_eq!(reverted[1].root, root2);
+        assert_eq!(reverted[1].prev_state.get(&key2), Some(&None));
+        assert_eq!(
+            reverted[1].prev_state.get(&key1),
+            Some(&Some((vec![bytes32!("val1")], 0)))
+        );
+        assert_eq!(journal.compute_root(), root1);
+        assert_eq!(
+            journal.get(&key1, false).map(|v| v.0),
+            Some(vec![bytes32!("val1")])
+        );
+        assert_eq!(journal.get(&key2, false), None);
+        // head can move on after revert
+        journal.update(&key2, &vec![bytes32!("val3")], 0);
+        journal.update(&key1, &vec![bytes32!("val2")], 0);
+        assert_eq!(journal.commit().unwrap().0, root2);
+    }
+
+    #[test]
+    fn test_commit_changeset_and_subscriber() {
+        let journal = JournaledTrie::new(ZkTrieStateDb::new_empty(InMemoryTrieDb::default()));
+        let address = Address::with_last_byte(7);
+        let mut fields = vec![[0u8; 32]; JZKT_ACCOUNT_FIELDS_COUNT as usize];
+        fields[JZKT_ACCOUNT_BALANCE_FIELD as usize][0] = 100;
+        fields[JZKT_ACCOUNT_NONCE_FIELD as usize][0] = 1;
+        let slot_key = JournaledTrie::<ZkTrieStateDb<InMemoryTrieDb>>::storage_key(
+            &address,
+            &bytes32!("slot1"),
+        );
+        journal.update(&address.into_word().0, &fields, 0);
+        journal.update(&slot_key, &vec![bytes32!("val1")], 0);
+        journal.commit().unwrap();
+        let changesets = Arc::new(Mutex::new(Vec::new()));
+        journal.subscribe({
+            let changesets = changesets.clone();
+            move |changeset| changesets.lock().unwrap().push(changeset.clone())
+        });
+        fields[JZKT_ACCOUNT_BALANCE_FIELD as usize][0] = 50;
+        journal.update(&address.into_word().0, &fields, 0);
+        journal.update(&slot_key, &vec![bytes32!("val2")], 0);
+        journal.remove(&bytes32!("key1"));
+        let (changeset, _) = journal.commit_changeset().unwrap();
+        assert_eq!(changesets.lock().unwrap().as_slice(), &[changeset.clone()]);
+        let accounts = changeset.accounts();
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].account.address, address);
+        assert_eq!(accounts[0].account.balance, U256::from(50));
+        assert_eq!(accounts[0].account.nonce, 1);
+        assert_eq!(accounts[0].prev.as_ref().unwrap().balance, U256::from(100));
+        let storage = changeset.storage();
+        assert_eq!(storage.len(), 1);
+        assert_eq!(storage[0].key, slot_key);
+        assert_eq!(storage[0].prev, Some(bytes32!("val1")));
+        assert_eq!(storage[0].value, bytes32!("val2"));
+        assert_eq!(changeset.removed(), vec![bytes32!("key1")]);
+    }
+
+    #[test]
+    fn test_fork_drop_and_merge() {
+        let journal = JournaledTrie::new(ZkTrieStateDb::new_empty(InMemoryTrieDb::default()));
+        let key1 = bytes32!("key1");
+        let key2 = bytes32!("key2");
+        let key3 = bytes32!("key3");
+        journal.update(&key1, &vec![bytes32!("val1")], 0);
+        let (root1, _) = journal.commit().unwrap();
+        // dropped fork doesn't affect parent
+        let fork = journal.fork();
+        fork.update(&key1, &vec![bytes32!("val2")], 0);
+        assert_eq!(
+            fork.get(&key1, false).map(|v| v.0),
+            Some(vec![bytes32!("val2")])
+        );
+        drop(fork);
+        assert_eq!(
+            journal.get(&key1, false).map(|v| v.0),
+            Some(vec![bytes32!("val1")])
+        );
+        // fork without conflicts can be merged even if parent moved on
+        let fork = journal.fork();
+        assert_eq!(fork.commit().err(), Some(ExitCode::WriteProtection));
+        assert!(fork.get(&key1, false).is_some());
+        fork.update(&key2, &vec![bytes32!("val2")], 0);
+        journal.update(&key3, &vec![bytes32!("val3")], 0);
+        journal.commit().unwrap();
+        journal.merge(fork).unwrap();
+        assert_eq!(
+            journal.get(&key2, false).map(|v| v.0),
+            Some(vec![bytes32!("val2")])
+        );
+        let (root2, _) = journal.commit().unwrap();
+        assert_ne!(root1, root2);
+        // fork that read a key changed by parent can't be merged
+        let fork = journal.fork();
+        assert!(fork.get(&key1, false).is_some());
+        fork.update(&key2, &vec![bytes32!("val4")], 0);
+        journal.update(&key1, &vec![bytes32!("val5")], 0);
+        assert_eq!(
+            journal.merge(fork),
+            Err(JournalMergeError::Conflicts(vec![key1]))
+        );
+        // only forks of other journals can be merged
+        assert_eq!(
+            journal.merge(journal.clone()),
+            Err(JournalMergeError::SelfMerge)
+        );
+        let other = JournaledTrie::new(ZkTrieStateDb::new_empty(InMemoryTrieDb::default()));
+        assert_eq!(journal.merge(other), Err(JournalMergeError::NotFork));
+    }
+
+    #[test]
+    fn test_transient_storage() {
+        let journal = JournaledTrie::new(ZkTrieStateDb::new_empty(InMemoryTrieDb::default()));
+        let empty_root = journal.compute_root();
+        journal.transient_update(&bytes32!("key1"), &bytes32!("val1"));
+        assert_eq!(journal.transient_get(&bytes32!("key1")), Some(bytes32!("val1")));
+        // transient slots are not visible as trie keys
+        assert_eq!(journal.get(&bytes32!("key1"), false), None);
+        // nested call is reverted
+        let checkpoint = journal.checkpoint();
+        journal.transient_update(&bytes32!("key1"), &bytes32!("val2"));
+        journal.transient_update(&bytes32!("key2"), &bytes32!("val3"));
+        journal.rollback(checkpoint);
+        assert_eq!(journal.transient_get(&bytes32!("key1")), Some(bytes32!("val1")));
+        assert_eq!(journal.transient_get(&bytes32!("key2")), None);
+        // end of transaction discards transient storage and doesn't touch the trie
+        let (root, _) = journal.commit().unwrap();
+        assert_eq!(root, empty_root);
+        assert_eq!(journal.transient_get(&bytes32!("key1")), None);
+    }
+
+    #[test]
+    fn test_warm_and_cold_access() {
+        let journal = JournaledTrie::new(ZkTrieStateDb::new_empty(InMemoryTrieDb::default()));
+        // first access is cold, the next ones are warm
+        assert!(journal.access(&bytes32!("key1")));
+        assert!(!journal.access(&bytes32!("key1")));
+        // warming inside reverted frame is discarded
+        let checkpoint = journal.checkpoint();
+        assert!(journal.access(&bytes32!("key2")));
+        journal.rollback(checkpoint);
+        assert!(journal.access(&bytes32!("key2")));
+        assert!(!journal.access(&bytes32!("key1")));
+        // access list is reset at the end of transaction
         journal.commit().unwrap();
-        assert_eq!(code1, journal.preimage(&code1_hash));
+        assert!(journal.access(&bytes32!("key1")));
     }
 
     #[test]
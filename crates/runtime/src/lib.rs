@@ -15,7 +15,12 @@ mod journal;
 
 pub use journal::*;
 
+mod changeset;
+
+pub use changeset::*;
+
 pub mod mptrie;
+pub mod parallel;
 #[cfg(test)]
 mod tests;
 pub mod types;
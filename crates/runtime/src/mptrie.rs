@@ -1,4 +1,7 @@
-use crate::{storage::TrieStorage, types::TrieDb};
+use crate::{
+    storage::TrieStorage,
+    types::{TrieDb, TrieDbNamespace},
+};
 use eth_trie::{EthTrie, Trie};
 use fluentbase_types::{Bytes, ExitCode};
 use hex_literal::hex;
@@ -45,6 +48,17 @@ impl<DB: eth_trie::DB + TrieDb> TrieStorage for MPTrieStateDb<DB> {
         true
     }
 
+    fn snapshot(&self, root32: &[u8]) -> Option<Self> {
+        if root32 != EMPTY_ROOT_HASH
+            && eth_trie::DB::get(self.storage.as_ref(), root32)
+                .unwrap_or_default()
+                .is_none()
+        {
+            return None;
+        }
+        Some(Self::new_opened(self.storage.clone(), root32))
+    }
+
     fn compute_root(&self) -> [u8; 32] {
         let mut trie = self.trie.as_ref().unwrap().borrow_mut();
         trie.root_hash().map_or(EMPTY_ROOT_HASH, |v| v.0)
@@ -101,13 +115,25 @@ impl<DB: eth_trie::DB + TrieDb> TrieStorage for MPTrieStateDb<DB> {
         p.map_or(None, |v| Some(v))
     }
 
-    fn get_preimage(&mut self, key: &[u8]) -> Option<Bytes> {
-        let r = self.storage.get(key).unwrap_or_default();
+    fn get_preimage(&mut self, namespace: TrieDbNamespace, key: &[u8]) -> Option<Bytes> {
+        // trie nodes are stored under raw keys by `EthTrie`, so preimages are always prefixed
+        let r = eth_trie::DB::get(self.storage.as_ref(), &namespace.prefixed_key(key))
+            .unwrap_or_default();
         r.map_or(None, |v| Some(Bytes::copy_from_slice(&v)))
     }
 
-    fn update_preimage(&mut self, key: &[u8], value: Bytes) {
-        let _ = self.storage.insert(key, value.to_vec());
+    fn update_preimage(
+        &mut self,
+        namespace: TrieDbNamespace,
+        key: &[u8],
+        value: Bytes,
+    ) -> Result<(), ExitCode> {
+        if namespace == TrieDbNamespace::Node {
+            return Err(ExitCode::PersistentStorageError);
+        }
+        self.storage
+            .insert(&namespace.prefixed_key(key), value.to_vec())
+            .map_err(|_| ExitCode::PersistentStorageError)
     }
 }
 
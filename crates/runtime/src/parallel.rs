@@ -0,0 +1,148 @@
+use crate::{JournaledTrie, TrieStorage};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+/// Optimistic parallel transaction executor (Block-STM like).
+///
+/// All transactions are executed concurrently, each one inside its own fork of the journal, that
+/// records read and write sets. Then forks are merged into the journal in block order, if
+/// transaction touched keys that were changed by the previous transactions then it's re-executed
+/// on top of the latest state. It makes the final state equal to the sequential execution.
+pub struct ParallelExecutor<DB: TrieStorage> {
+    journal: JournaledTrie<DB>,
+    concurrency: usize,
+}
+
+/// Result of the parallel execution
+pub struct ParallelExecutionResult<R> {
+    /// Results of the transactions in block order
+    pub results: Vec<R>,
+    /// Number of transactions that were re-executed because of conflicts
+    pub reexecuted: usize,
+}
+
+impl<DB: TrieStorage + Send + Sync> ParallelExecutor<DB> {
+    pub fn new(journal: JournaledTrie<DB>) -> Self {
+        Self {
+            journal,
+            concurrency: thread::available_parallelism()
+                .map(|v| v.get())
+                .unwrap_or(1),
+        }
+    }
+
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Executes transactions and applies their changes to the journal as uncommitted changes.
+    ///
+    /// Executor calls `f` with a journal view for each transaction, so `f` must not touch the
+    /// original journal directly.
+    pub fn execute<T, R, F>(&self, transactions: &[T], f: F) -> ParallelExecutionResult<R>
+    where
+        T: Sync,
+        R: Send,
+        F: Fn(&T, &JournaledTrie<DB>) -> R + Sync,
+    {
+        let next_tx = AtomicUsize::new(0);
+        let executed = Mutex::new(
+            (0..transactions.len())
+                .map(|_| None)
+                .collect::<Vec<Option<(JournaledTrie<DB>, R)>>>(),
+        );
+        // optimistically execute all transactions on top of the same state
+        thread::scope(|scope| {
+            for _ in 0..self.concurrency.min(transactions.len()) {
+                scope.spawn(|| loop {
+                    let index = next_tx.fetch_add(1, Ordering::SeqCst);
+                    if index >= transactions.len() {
+                        break;
+                    }
+                    let fork = self.journal.fork_head();
+                    let result = f(&transactions[index], &fork);
+                    executed.lock().unwrap()[index] = Some((fork, result));
+                });
+            }
+        });
+        // validate and merge in block order, re-execute transactions with conflicts
+        let mut results = Vec::with_capacity(transactions.len());
+        let mut reexecuted = 0;
+        for (index, entry) in executed.into_inner().unwrap().into_iter().enumerate() {
+            let (fork, result) = entry.expect("transaction is not executed");
+            let result = match self.journal.merge(fork) {
+                Ok(()) => result,
+                Err(_) => {
+                    reexecuted += 1;
+                    let fork = self.journal.fork_head();
+                    let result = f(&transactions[index], &fork);
+                    self.journal
+                        .merge(fork)
+                        .expect("journal can't be changed during re-execution");
+                    result
+                }
+            };
+            results.push(result);
+        }
+        ParallelExecutionResult {
+            results,
+            reexecuted,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parallel::ParallelExecutor,
+        types::InMemoryTrieDb,
+        zktrie::ZkTrieStateDb,
+        JournaledTrie,
+    };
+    use fluentbase_types::IJournaledTrie;
+
+    fn counter_key(index: u8) -> [u8; 32] {
+        let mut key = [0u8; 32];
+        key[31] = index;
+        key
+    }
+
+    /// Moves one unit from the first counter to the second one
+    fn transfer(journal: &JournaledTrie<ZkTrieStateDb<InMemoryTrieDb>>, from: u8, to: u8) -> u8 {
+        let read = |key: &[u8; 32]| journal.get(key, false).map(|v| v.0[0][0]).unwrap_or(100);
+        let (from, to) = (counter_key(from), counter_key(to));
+        let balance = read(&from);
+        let mut value = [0u8; 32];
+        value[0] = balance - 1;
+        journal.update(&from, &vec![value], 0);
+        value[0] = read(&to) + 1;
+        journal.update(&to, &vec![value], 0);
+        balance
+    }
+
+    #[test]
+    fn test_parallel_root_matches_sequential() {
+        let transactions = vec![(1, 2), (3, 4), (2, 5), (6, 7), (5, 1), (8, 9), (1, 8)];
+        let sequential = JournaledTrie::new(ZkTrieStateDb::new_empty(InMemoryTrieDb::default()));
+        let expected = transactions
+            .iter()
+            .map(|(from, to)| transfer(&sequential, *from, *to))
+            .collect::<Vec<_>>();
+        let journal = JournaledTrie::new(ZkTrieStateDb::new_empty(InMemoryTrieDb::default()));
+        let result = ParallelExecutor::new(journal.clone())
+            .with_concurrency(4)
+            .execute(&transactions, |(from, to), journal| {
+                transfer(journal, *from, *to)
+            });
+        assert_eq!(result.results, expected);
+        // transactions 2, 4 and 6 depend on the previous ones
+        assert_eq!(result.reexecuted, 3);
+        assert_eq!(journal.commit().unwrap().0, sequential.commit().unwrap().0);
+    }
+}
@@ -12,20 +12,20 @@ use fluentbase_poseidon::poseidon_hash;
 use fluentbase_types::{
     create_shared_import_linker,
     create_sovereign_import_linker,
+    create_state_router_config,
     Bytes,
     EmptyJournalTrie,
     ExitCode,
     IJournaledTrie,
-    SysFuncIdx::SYS_STATE,
     F254,
+    JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD,
     POSEIDON_EMPTY,
-    STATE_DEPLOY,
-    STATE_MAIN,
+    STATE_VIEW,
 };
-use hashbrown::{hash_map::Entry, HashMap};
+use hashbrown::HashMap;
 use rwasm::{
     core::{ImportLinker, Trap},
-    engine::{bytecode::Instruction, DropKeep, RwasmConfig, StateRouterConfig},
+    engine::{DropKeep, RwasmConfig},
     instruction_set,
     rwasm::RwasmModule,
     AsContextMut,
@@ -40,9 +40,9 @@ use rwasm::{
     Value,
 };
 use std::{
-    cell::RefCell,
     fmt::{Debug, Formatter},
     mem::take,
+    sync::{Arc, OnceLock, RwLock},
 };
 
 pub type DefaultEmptyRuntimeDatabase = JournaledTrie<ZkTrieStateDb<InMemoryTrieDb>>;
@@ -86,6 +86,9 @@ pub struct RuntimeContext<DB: IJournaledTrie> {
     pub(crate) is_shared: bool,
     pub(crate) input: Vec<u8>,
     pub(crate) depth: u32,
+    pub(crate) is_static: bool,
+    /// Journal position where static mode was entered, static frame can't roll back before it
+    pub(crate) static_checkpoint: Option<u64>,
     // context outputs
     pub(crate) execution_result: ExecutionResult,
     // storage
@@ -107,6 +110,8 @@ impl<DB: IJournaledTrie> Default for RuntimeContext<DB> {
             is_shared: false,
             input: vec![],
             depth: 0,
+            is_static: false,
+            static_checkpoint: None,
             execution_result: Default::default(),
             jzkt: None,
         }
@@ -162,6 +167,16 @@ impl<DB: IJournaledTrie> RuntimeContext<DB> {
         self
     }
 
+    pub fn with_is_static(mut self, is_static: bool) -> Self {
+        self.is_static = is_static;
+        self
+    }
+
+    pub fn with_static_checkpoint(mut self, static_checkpoint: Option<u64>) -> Self {
+        self.static_checkpoint = static_checkpoint;
+        self
+    }
+
     pub fn jzkt(&mut self) -> &DB {
         self.jzkt.as_ref().expect("jzkt is not initialized")
     }
@@ -170,6 +185,10 @@ impl<DB: IJournaledTrie> RuntimeContext<DB> {
         self.depth
     }
 
+    pub fn is_static(&self) -> bool {
+        self.is_static
+    }
+
     pub fn exit_code(&self) -> i32 {
         self.execution_result.exit_code
     }
@@ -224,31 +243,36 @@ impl ExecutionResult {
     }
 }
 
+/// Compiled module cache that can be shared between runtimes across threads.
+///
+/// All modules are compiled with the same engine and keyed by rWASM code hash.
 pub struct CachingRuntime {
+    engine: Engine,
     // TODO(dmitry123): "add expiration to this map to avoid memory leak"
-    modules: HashMap<F254, Module>,
+    modules: RwLock<HashMap<F254, Arc<Module>>>,
 }
 
 impl CachingRuntime {
     pub fn new() -> Self {
         Self {
-            modules: HashMap::new(),
+            engine: Self::new_engine(),
+            modules: RwLock::new(HashMap::new()),
         }
     }
 
+    /// Returns process-wide cache that is used by all runtimes.
+    pub fn shared() -> &'static CachingRuntime {
+        static CACHING_RUNTIME: OnceLock<CachingRuntime> = OnceLock::new();
+        CACHING_RUNTIME.get_or_init(CachingRuntime::new)
+    }
+
     fn new_engine() -> Engine {
         // we can safely use sovereign import linker because all protected are filtered out during
         // translation process
         let import_linker = Runtime::new_sovereign_linker();
         let mut config = RwasmModule::default_config(None);
         config.rwasm_config(RwasmConfig {
-            state_router: Some(StateRouterConfig {
-                states: Box::new([
-                    ("deploy".to_string(), STATE_DEPLOY),
-                    ("main".to_string(), STATE_MAIN),
-                ]),
-                opcode: Instruction::Call(SYS_STATE.into()),
-            }),
+            state_router: Some(create_state_router_config()),
             entrypoint_name: None,
             import_linker: Some(import_linker),
             wrap_import_functions: true,
@@ -260,16 +284,19 @@ impl CachingRuntime {
         Engine::new(&config)
     }
 
+    pub fn engine(&self) -> &Engine {
+        &self.engine
+    }
+
+    /// Returns cached module or compiles and caches a new one.
     pub fn init_module(
-        &mut self,
-        engine: &Engine,
+        &self,
         rwasm_hash: F254,
         rwasm_bytecode: &[u8],
-    ) -> Result<&Module, RuntimeError> {
-        let entry = match self.modules.entry(rwasm_hash) {
-            Entry::Occupied(_) => return Err(RuntimeError::UnloadedModule(rwasm_hash)),
-            Entry::Vacant(entry) => entry,
-        };
+    ) -> Result<Arc<Module>, RuntimeError> {
+        if let Some(module) = self.resolve_module(&rwasm_hash) {
+            return Ok(module);
+        }
         // empty bytecode we can't execute so just return Ok exit code
         let reduced_module = if !rwasm_bytecode.is_empty() {
             RwasmModule::new(rwasm_bytecode).map_err(Into::<RuntimeError>::into)?
@@ -278,21 +305,22 @@ impl CachingRuntime {
                 Return(DropKeep::none())
             })
         };
-        // let engine = Self::new_engine();
-        let module_builder = reduced_module.to_module_builder(engine);
-        let module = module_builder.finish();
-        Ok(entry.insert(module))
+        // compile outside of the lock, if another thread did the same then its module wins
+        let module = Arc::new(reduced_module.to_module_builder(&self.engine).finish());
+        Ok(self
+            .modules
+            .write()
+            .unwrap()
+            .entry(rwasm_hash)
+            .or_insert(module)
+            .clone())
     }
 
-    pub fn resolve_module(&self, rwasm_hash: &F254) -> Option<&Module> {
-        self.modules.get(rwasm_hash)
+    pub fn resolve_module(&self, rwasm_hash: &F254) -> Option<Arc<Module>> {
+        self.modules.read().unwrap().get(rwasm_hash).cloned()
     }
 }
 
-thread_local! {
-    static CACHING_RUNTIME: RefCell<CachingRuntime> = RefCell::new(CachingRuntime::new());
-}
-
 pub struct Runtime<DB: IJournaledTrie> {
     pub(crate) store: Store<RuntimeContext<DB>>,
     pub(crate) linker: Linker<RuntimeContext<DB>>,
@@ -342,14 +370,19 @@ impl<DB: IJournaledTrie> Runtime<DB> {
         // make sure bytecode hash is resolved
         runtime_context.bytecode = runtime_context.bytecode.with_resolved_hash();
 
-        // use existing engine or create a new one
-        let engine = CACHING_RUNTIME.with_borrow_mut(|caching_runtime| {
-            let rwasm_hash = runtime_context.bytecode.resolve_hash();
-            caching_runtime
-                .resolve_module(&rwasm_hash)
-                .map(|module| module.engine.clone())
-                .unwrap_or_else(|| CachingRuntime::new_engine())
-        });
+        // view entrypoint can't modify the state
+        if runtime_context.state == STATE_VIEW {
+            runtime_context.is_static = true;
+        }
+        if runtime_context.is_static && runtime_context.static_checkpoint.is_none() {
+            runtime_context.static_checkpoint = runtime_context
+                .jzkt
+                .as_ref()
+                .map(|jzkt| jzkt.checkpoint().to_u64());
+        }
+
+        // all modules are compiled with the shared engine
+        let engine = CachingRuntime::shared().engine().clone();
 
         // create new linker and store (it shares same engine resources)
         let mut store = Store::<RuntimeContext<DB>>::new(&engine, runtime_context);
@@ -371,51 +404,44 @@ impl<DB: IJournaledTrie> Runtime<DB> {
     }
 
     pub fn call(&mut self) -> Result<ExecutionResult, RuntimeError> {
-        let instance = CACHING_RUNTIME.with_borrow_mut(|caching_runtime| {
-            let bytecode_repr = take(&mut self.store.data_mut().bytecode);
-
-            // resolve cached module or init it
-            let module = match &bytecode_repr {
-                BytecodeOrHash::Bytecode(bytecode, hash) => {
-                    let hash = hash.unwrap_or_else(|| F254::from(poseidon_hash(&bytecode)));
-                    // if we have cached module then use it, otherwise create new one and cache
-                    if let Some(module) = caching_runtime.resolve_module(&hash) {
-                        Ok(module)
-                    } else {
-                        caching_runtime.init_module(self.store.engine(), hash, &bytecode)
-                    }
-                }
-                BytecodeOrHash::Hash(hash) => {
-                    // if we have only hash then try to load module or fail fast
-                    match caching_runtime.resolve_module(hash) {
-                        Some(module) => Ok(module),
-                        None => {
-                            let rwasm_bytecode = self
-                                .store
-                                .data_mut()
-                                .jzkt
-                                .as_ref()
-                                .ok_or(RuntimeError::UnloadedModule(*hash))?
-                                .preimage(hash);
-                            caching_runtime.init_module(self.store.engine(), *hash, &rwasm_bytecode)
-                        }
+        let caching_runtime = CachingRuntime::shared();
+        let bytecode_repr = take(&mut self.store.data_mut().bytecode);
+
+        // resolve cached module or init it
+        let module = match &bytecode_repr {
+            BytecodeOrHash::Bytecode(bytecode, hash) => {
+                let hash = hash.unwrap_or_else(|| F254::from(poseidon_hash(&bytecode)));
+                // if we have cached module then use it, otherwise create new one and cache
+                caching_runtime.init_module(hash, &bytecode)
+            }
+            BytecodeOrHash::Hash(hash) => {
+                // if we have only hash then try to load module or fail fast
+                match caching_runtime.resolve_module(hash) {
+                    Some(module) => Ok(module),
+                    None => {
+                        let rwasm_bytecode = self
+                            .store
+                            .data_mut()
+                            .jzkt
+                            .as_ref()
+                            .ok_or(RuntimeError::UnloadedModule(*hash))?
+                            .preimage(JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD, hash);
+                        caching_runtime.init_module(*hash, &rwasm_bytecode)
                     }
                 }
-            }?;
-
-            // return bytecode back
-            self.store.data_mut().bytecode = bytecode_repr;
-
-            // init instance
-            let instance = self
-                .linker
-                .instantiate(&mut self.store, &module)
-                .map_err(Into::<RuntimeError>::into)?
-                .start(&mut self.store)
-                .map_err(Into::<RuntimeError>::into)?;
-
-            Ok::<Instance, RuntimeError>(instance)
-        })?;
+            }
+        }?;
+
+        // return bytecode back
+        self.store.data_mut().bytecode = bytecode_repr;
+
+        // init instance
+        let instance = self
+            .linker
+            .instantiate(&mut self.store, &module)
+            .map_err(Into::<RuntimeError>::into)?
+            .start(&mut self.store)
+            .map_err(Into::<RuntimeError>::into)?;
 
         let mut next_result = instance
             .get_func(&mut self.store, "main")
@@ -1,8 +1,16 @@
+use crate::types::TrieDbNamespace;
 use fluentbase_types::{Bytes, ExitCode};
 
 pub trait TrieStorage {
     fn open(&mut self, root32: &[u8]) -> bool;
 
+    /// Opens one more view at the given root that shares the same node backend.
+    ///
+    /// Returns `None` if the root is unknown to the backend.
+    fn snapshot(&self, root32: &[u8]) -> Option<Self>
+    where
+        Self: Sized;
+
     fn compute_root(&self) -> [u8; 32];
 
     fn get(&self, key: &[u8]) -> Option<(Vec<[u8; 32]>, u32)>;
@@ -18,13 +26,18 @@ pub trait TrieStorage {
 
     fn proof(&self, key: &[u8; 32]) -> Option<Vec<Vec<u8>>>;
 
-    fn get_preimage(&mut self, key: &[u8]) -> Option<Bytes>;
+    fn get_preimage(&mut self, namespace: TrieDbNamespace, key: &[u8]) -> Option<Bytes>;
 
-    fn preimage_size(&mut self, key: &[u8]) -> u32 {
-        self.get_preimage(key)
+    fn preimage_size(&mut self, namespace: TrieDbNamespace, key: &[u8]) -> u32 {
+        self.get_preimage(namespace, key)
             .map(|v| v.len() as u32)
             .unwrap_or_default()
     }
 
-    fn update_preimage(&mut self, key: &[u8], value: Bytes);
+    fn update_preimage(
+        &mut self,
+        namespace: TrieDbNamespace,
+        key: &[u8],
+        value: Bytes,
+    ) -> Result<(), ExitCode>;
 }
@@ -1,13 +1,18 @@
-use crate::{runtime::Runtime, DefaultEmptyRuntimeDatabase, RuntimeContext};
+use crate::{runtime::Runtime, CachingRuntime, DefaultEmptyRuntimeDatabase, RuntimeContext};
+use fluentbase_poseidon::poseidon_hash;
 use fluentbase_types::{
     create_sovereign_import_linker,
-    SysFuncIdx::SYS_STATE,
+    create_state_router_config,
+    ExitCode,
+    F254,
     STATE_DEPLOY,
+    STATE_FALLBACK,
     STATE_MAIN,
+    STATE_VIEW,
 };
 use hex_literal::hex;
 use rwasm::{
-    engine::{bytecode::Instruction, RwasmConfig, StateRouterConfig},
+    engine::RwasmConfig,
     rwasm::{BinaryFormat, RwasmModule},
 };
 
@@ -16,13 +21,7 @@ pub(crate) fn wat2rwasm(wat: &str) -> Vec<u8> {
     let wasm_binary = wat::parse_str(wat).unwrap();
     let mut rwasm_config = RwasmModule::default_config(Some(import_linker));
     rwasm_config.rwasm_config(RwasmConfig {
-        state_router: Some(StateRouterConfig {
-            states: Box::new([
-                ("deploy".to_string(), STATE_DEPLOY),
-                ("main".to_string(), STATE_MAIN),
-            ]),
-            opcode: Instruction::Call(SYS_STATE.into()),
-        }),
+        state_router: Some(create_state_router_config()),
         entrypoint_name: None,
         import_linker: Some(create_sovereign_import_linker()),
         wrap_import_functions: true,
@@ -127,3 +126,129 @@ fn test_keccak256() {
         execution_result.output.as_slice()
     );
 }
+
+#[test]
+fn test_module_cache_shared_between_threads() {
+    let rwasm_binary = wat2rwasm(
+        r#"
+(module
+  (func $main
+    i32.const 7
+    drop
+    )
+  (export "main" (func $main)))
+    "#,
+    );
+    let rwasm_hash = F254::from(poseidon_hash(&rwasm_binary));
+    let handles = (0..4)
+        .map(|_| {
+            let rwasm_binary = rwasm_binary.clone();
+            std::thread::spawn(move || {
+                let ctx = RuntimeContext::new(rwasm_binary).with_fuel_limit(10_000_000);
+                Runtime::<DefaultEmptyRuntimeDatabase>::run_with_context(ctx)
+                    .unwrap()
+                    .exit_code
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), 0);
+    }
+    // module is compiled once and visible from any thread
+    let module = CachingRuntime::shared().resolve_module(&rwasm_hash).unwrap();
+    let same_module = CachingRuntime::shared()
+        .init_module(rwasm_hash, &rwasm_binary)
+        .unwrap();
+    assert!(std::sync::Arc::ptr_eq(&module, &same_module));
+}
+
+#[test]
+fn test_static_call_write_protection() {
+    let rwasm_binary = wat2rwasm(
+        r#"
+(module
+  (type (;0;) (func (param i32 i32)))
+  (type (;1;) (func))
+  (import "fluentbase_v1alpha" "_jzkt_transient_update" (func $_jzkt_transient_update (type 0)))
+  (func $main (type 1)
+    i32.const 0
+    i32.const 32
+    call $_jzkt_transient_update
+    )
+  (memory (;0;) 1)
+  (export "main" (func $main)))
+    "#,
+    );
+    for (is_static, exit_code) in [(false, ExitCode::Ok), (true, ExitCode::WriteProtection)] {
+        let ctx = RuntimeContext::new(rwasm_binary.clone())
+            .with_fuel_limit(1_000_000)
+            .with_jzkt(DefaultEmptyRuntimeDatabase::default())
+            .with_is_static(is_static);
+        let execution_result =
+            Runtime::<DefaultEmptyRuntimeDatabase>::run_with_context(ctx).unwrap();
+        assert_eq!(execution_result.exit_code, exit_code.into_i32());
+    }
+}
+
+#[test]
+fn test_view_entrypoint() {
+    let rwasm_binary = wat2rwasm(
+        r#"
+(module
+  (type (;0;) (func (param i32 i32)))
+  (type (;1;) (func))
+  (import "fluentbase_v1alpha" "_jzkt_transient_update" (func $_jzkt_transient_update (type 0)))
+  (func $main (type 1)
+    i32.const 0
+    i32.const 32
+    call $_jzkt_transient_update
+    )
+  (func $view (type 1)
+    )
+  (func $fallback (type 1)
+    i32.const 0
+    i32.const 32
+    call $_jzkt_transient_update
+    )
+  (memory (;0;) 1)
+  (export "main" (func $main))
+  (export "view" (func $view))
+  (export "fallback" (func $fallback)))
+    "#,
+    );
+    let run = |state: u32| {
+        let ctx = RuntimeContext::new(rwasm_binary.clone())
+            .with_fuel_limit(1_000_000)
+            .with_jzkt(DefaultEmptyRuntimeDatabase::default())
+            .with_state(state);
+        Runtime::<DefaultEmptyRuntimeDatabase>::run_with_context(ctx)
+            .unwrap()
+            .exit_code
+    };
+    assert_eq!(run(STATE_MAIN), ExitCode::Ok.into_i32());
+    assert_eq!(run(STATE_VIEW), ExitCode::Ok.into_i32());
+    assert_eq!(run(STATE_FALLBACK), ExitCode::Ok.into_i32());
+    // view entrypoint is executed in static mode, even if it's exported by another function
+    let rwasm_binary = wat2rwasm(
+        r#"
+(module
+  (type (;0;) (func (param i32 i32)))
+  (type (;1;) (func))
+  (import "fluentbase_v1alpha" "_jzkt_transient_update" (func $_jzkt_transient_update (type 0)))
+  (func $main (type 1)
+    i32.const 0
+    i32.const 32
+    call $_jzkt_transient_update
+    )
+  (memory (;0;) 1)
+  (export "main" (func $main))
+  (export "view" (func $main)))
+    "#,
+    );
+    let ctx = RuntimeContext::new(rwasm_binary)
+        .with_fuel_limit(1_000_000)
+        .with_jzkt(DefaultEmptyRuntimeDatabase::default())
+        .with_state(STATE_VIEW);
+    let execution_result = Runtime::<DefaultEmptyRuntimeDatabase>::run_with_context(ctx).unwrap();
+    assert_eq!(execution_result.exit_code, ExitCode::WriteProtection.into_i32());
+}
@@ -1,57 +1,162 @@
 use eth_trie::DB;
-use fluentbase_types::{Bytes, F254};
+use fluentbase_poseidon::poseidon_hash;
+use fluentbase_types::{
+    Bytes,
+    ExitCode,
+    JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD,
+    JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD,
+    F254,
+};
+use fluentbase_zktrie::{Node, PoseidonHash};
 use hashbrown::HashMap;
+use keccak_hash::keccak;
 use rwasm::{rwasm::BinaryFormatError, Error as RwasmError};
 
-pub trait TrieDb {
-    fn get_node(&mut self, key: &[u8]) -> Option<Bytes>;
+/// Keyspace that every [`TrieDb`] entry belongs to.
+///
+/// Trie nodes and different kinds of preimages are stored in separate namespaces, so a node hash
+/// can never collide with a bytecode hash (or any other preimage hash) inside the same backend.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum TrieDbNamespace {
+    /// Trie nodes keyed by node hash
+    Node = 0x01,
+    /// Source (EVM/WASM) bytecode keyed by keccak256 code hash
+    Bytecode = 0x02,
+    /// rWASM bytecode keyed by poseidon code hash
+    Rwasm = 0x03,
+    /// Arbitrary hash preimages (key preimages, custom account fields etc)
+    Preimage = 0x04,
+}
 
-    fn update_node(&mut self, key: &[u8], value: Bytes);
+impl TrieDbNamespace {
+    /// Returns namespace for the preimage of the account field with the given index.
+    pub fn from_account_field(field: u32) -> Self {
+        match field {
+            JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD => TrieDbNamespace::Bytecode,
+            JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD => TrieDbNamespace::Rwasm,
+            _ => TrieDbNamespace::Preimage,
+        }
+    }
 
-    fn get_preimage(&mut self, key: &[u8]) -> Option<Bytes>;
+    /// Returns a flat storage key `prefix || key` for backends without column families.
+    pub fn prefixed_key(&self, key: &[u8]) -> Vec<u8> {
+        let mut storage_key = Vec::with_capacity(key.len() + 1);
+        storage_key.push(*self as u8);
+        storage_key.extend_from_slice(key);
+        storage_key
+    }
 
-    fn update_preimage(&mut self, key: &[u8], value: Bytes);
+    /// Detects namespace of an entry that was written with the legacy flat layout, where nodes
+    /// and preimages shared one keyspace.
+    pub fn classify_legacy_entry(key: &[u8], value: &[u8]) -> Self {
+        if let Ok(node) = Node::<PoseidonHash>::from_bytes(value) {
+            if node.hash().raw_bytes() == key {
+                return TrieDbNamespace::Node;
+            }
+        }
+        if poseidon_hash(value) == key {
+            TrieDbNamespace::Rwasm
+        } else if keccak(value).as_bytes() == key {
+            TrieDbNamespace::Bytecode
+        } else {
+            TrieDbNamespace::Preimage
+        }
+    }
 }
 
-#[derive(Default, Clone)]
-pub struct InMemoryTrieDb {
-    nodes: HashMap<Bytes, Bytes>,
-    preimages: HashMap<Bytes, Bytes>,
-}
+pub trait TrieDb {
+    fn get(&mut self, namespace: TrieDbNamespace, key: &[u8]) -> Option<Bytes>;
+
+    fn update(&mut self, namespace: TrieDbNamespace, key: &[u8], value: Bytes);
 
-impl TrieDb for InMemoryTrieDb {
     fn get_node(&mut self, key: &[u8]) -> Option<Bytes> {
-        self.nodes.get(&Bytes::copy_from_slice(key)).cloned()
+        self.get(TrieDbNamespace::Node, key)
     }
 
     fn update_node(&mut self, key: &[u8], value: Bytes) {
-        self.nodes.insert(Bytes::copy_from_slice(key), value);
+        self.update(TrieDbNamespace::Node, key, value)
     }
 
-    fn get_preimage(&mut self, key: &[u8]) -> Option<Bytes> {
-        self.preimages.get(&Bytes::copy_from_slice(key)).cloned()
+    /// Preimages can't be read from the node namespace, so `None` is returned for it
+    fn get_preimage(&mut self, namespace: TrieDbNamespace, key: &[u8]) -> Option<Bytes> {
+        if namespace == TrieDbNamespace::Node {
+            return None;
+        }
+        self.get(namespace, key)
     }
 
-    fn update_preimage(&mut self, key: &[u8], value: Bytes) {
-        self.preimages.insert(Bytes::copy_from_slice(key), value);
+    /// Preimages can't be stored in the node namespace, otherwise they could replace trie nodes
+    fn update_preimage(
+        &mut self,
+        namespace: TrieDbNamespace,
+        key: &[u8],
+        value: Bytes,
+    ) -> Result<(), ExitCode> {
+        if namespace == TrieDbNamespace::Node {
+            return Err(ExitCode::PersistentStorageError);
+        }
+        self.update(namespace, key, value);
+        Ok(())
     }
 }
 
-impl TrieDb for eth_trie::MemoryDB {
-    fn get_node(&mut self, key: &[u8]) -> Option<Bytes> {
-        self.get(key).map_or(None, |v| v.map(|v| Bytes::from(v)))
+/// Copies entries written with the legacy flat keyspace layout into namespaced storage.
+///
+/// Legacy entries are not removed from the source, because for some backends (like
+/// [`eth_trie::MemoryDB`]) trie nodes are still addressed by raw keys. Returns number of migrated
+/// entries.
+pub fn migrate_legacy_trie_db<DB: TrieDb, I: IntoIterator<Item = (Bytes, Bytes)>>(
+    db: &mut DB,
+    entries: I,
+) -> usize {
+    let mut migrated = 0;
+    for (key, value) in entries.into_iter() {
+        let namespace = TrieDbNamespace::classify_legacy_entry(&key, &value);
+        db.update(namespace, &key, value);
+        migrated += 1;
     }
+    migrated
+}
 
-    fn update_node(&mut self, key: &[u8], value: Bytes) {
-        self.insert(key, value.into()).unwrap()
+#[derive(Default, Clone)]
+pub struct InMemoryTrieDb {
+    namespaces: HashMap<TrieDbNamespace, HashMap<Bytes, Bytes>>,
+}
+
+impl TrieDb for InMemoryTrieDb {
+    fn get(&mut self, namespace: TrieDbNamespace, key: &[u8]) -> Option<Bytes> {
+        self.namespaces
+            .get(&namespace)?
+            .get(&Bytes::copy_from_slice(key))
+            .cloned()
+    }
+
+    fn update(&mut self, namespace: TrieDbNamespace, key: &[u8], value: Bytes) {
+        self.namespaces
+            .entry(namespace)
+            .or_default()
+            .insert(Bytes::copy_from_slice(key), value);
     }
+}
 
-    fn get_preimage(&mut self, key: &[u8]) -> Option<Bytes> {
-        self.get(key).map_or(None, |v| v.map(|v| Bytes::from(v)))
+impl TrieDb for eth_trie::MemoryDB {
+    fn get(&mut self, namespace: TrieDbNamespace, key: &[u8]) -> Option<Bytes> {
+        // nodes are managed by `EthTrie` directly, so we must keep them under raw keys
+        let value = match namespace {
+            TrieDbNamespace::Node => DB::get(self, key),
+            _ => DB::get(self, &namespace.prefixed_key(key)),
+        };
+        value.map_or(None, |v| v.map(|v| Bytes::from(v)))
     }
 
-    fn update_preimage(&mut self, key: &[u8], value: Bytes) {
-        self.insert(key, value.into()).unwrap()
+    fn update(&mut self, namespace: TrieDbNamespace, key: &[u8], value: Bytes) {
+        match namespace {
+            TrieDbNamespace::Node => self.insert(key, value.into()).unwrap(),
+            _ => self
+                .insert(&namespace.prefixed_key(key), value.into())
+                .unwrap(),
+        }
     }
 }
 
@@ -92,3 +197,66 @@ rwasm_error!(rwasm::memory::MemoryError);
 rwasm_error!(rwasm::table::TableError);
 rwasm_error!(rwasm::linker::LinkerError);
 rwasm_error!(rwasm::module::ModuleError);
+
+#[cfg(test)]
+mod tests {
+    use super::{migrate_legacy_trie_db, InMemoryTrieDb, TrieDb, TrieDbNamespace};
+    use fluentbase_poseidon::poseidon_hash;
+    use fluentbase_types::Bytes;
+    use fluentbase_zktrie::{Byte32, Hash, Node, PoseidonHash};
+    use keccak_hash::keccak;
+
+    #[test]
+    fn test_migrate_legacy_trie_db() {
+        let node_key = [0x01u8; 32];
+        let node = Node::<PoseidonHash>::new_leaf(
+            Hash::from_bytes(&node_key),
+            1,
+            Byte32::from_vec_bytes(&node_key),
+            None,
+        )
+        .unwrap();
+        let node_value = Bytes::from(node.canonical_value());
+        let code = Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0xf3]);
+        let rwasm = Bytes::from_static(&[0xef, 0x00, 0x52, 0x01]);
+        let key_preimage = Bytes::from_static(&[0xaa; 20]);
+        let entries = [
+            (Bytes::copy_from_slice(node.hash().raw_bytes()), node_value.clone()),
+            (Bytes::copy_from_slice(keccak(&code).as_bytes()), code.clone()),
+            (Bytes::copy_from_slice(&poseidon_hash(&rwasm)), rwasm.clone()),
+            (Bytes::from_static(&[0xbb; 32]), key_preimage.clone()),
+        ];
+        for (key, value) in entries.iter() {
+            let namespace = TrieDbNamespace::classify_legacy_entry(key, value);
+            let expected = if value == &node_value {
+                TrieDbNamespace::Node
+            } else if value == &code {
+                TrieDbNamespace::Bytecode
+            } else if value == &rwasm {
+                TrieDbNamespace::Rwasm
+            } else {
+                TrieDbNamespace::Preimage
+            };
+            assert_eq!(namespace, expected);
+        }
+
+        let mut db = InMemoryTrieDb::default();
+        assert_eq!(migrate_legacy_trie_db(&mut db, entries.clone()), 4);
+        assert_eq!(db.get_node(&entries[0].0), Some(node_value));
+        assert_eq!(
+            db.get_preimage(TrieDbNamespace::Bytecode, &entries[1].0),
+            Some(code)
+        );
+        assert_eq!(
+            db.get_preimage(TrieDbNamespace::Rwasm, &entries[2].0),
+            Some(rwasm)
+        );
+        assert_eq!(
+            db.get_preimage(TrieDbNamespace::Preimage, &entries[3].0),
+            Some(key_preimage)
+        );
+        // entries don't leak into other namespaces
+        assert_eq!(db.get(TrieDbNamespace::Preimage, &entries[1].0), None);
+        assert_eq!(db.get(TrieDbNamespace::Bytecode, &entries[2].0), None);
+    }
+}
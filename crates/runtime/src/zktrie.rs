@@ -1,4 +1,7 @@
-use crate::{storage::TrieStorage, types::TrieDb};
+use crate::{
+    storage::TrieStorage,
+    types::{TrieDb, TrieDbNamespace},
+};
 use fluentbase_types::{Bytes, ExitCode, POSEIDON_EMPTY};
 use fluentbase_zktrie::{
     Byte32,
@@ -12,37 +15,23 @@ use fluentbase_zktrie::{
     ZkTrie,
 };
 use halo2curves::bn256::Fr;
-use std::{cell::RefCell, rc::Rc, sync::Arc};
+use std::sync::{Arc, Mutex};
 
 #[derive(Clone)]
-struct NodeDb<DB>(Rc<RefCell<DB>>);
-
-const STORAGE_PREFIX_NODE: u8 = 0x01;
-const STORAGE_PREFIX_PREIMAGE: u8 = 0x02;
-const STORAGE_PREFIX_CODE: u8 = 0x02;
-
-macro_rules! storage_key {
-    ($prefix:ident, $key:expr) => {{
-        assert_eq!($key.len(), 32);
-        let mut storage_key = [0u8; 33];
-        storage_key[0] = $prefix;
-        storage_key[1..].copy_from_slice($key);
-        storage_key
-    }};
-}
+struct NodeDb<DB>(Arc<Mutex<DB>>);
 
 impl<DB: TrieDb> Database for NodeDb<DB> {
     type Node = Node<PoseidonHash>;
 
     fn get_node(&self, key: &Hash) -> Result<Option<Arc<Self::Node>>, Error> {
-        match self.0.borrow_mut().get_node(key.raw_bytes()) {
+        match self.0.lock().unwrap().get_node(key.raw_bytes()) {
             Some(value) => Ok(Some(Arc::new(Node::from_bytes(&value)?))),
             None => Ok(None),
         }
     }
 
     fn update_node(&mut self, node: Self::Node) -> Result<Arc<Self::Node>, Error> {
-        self.0.borrow_mut().update_node(
+        self.0.lock().unwrap().update_node(
             node.hash().raw_bytes(),
             Bytes::copy_from_slice(&node.canonical_value()),
         );
@@ -52,15 +41,19 @@ impl<DB: TrieDb> Database for NodeDb<DB> {
 
 impl<'a, DB: TrieDb> PreimageDatabase for NodeDb<DB> {
     fn update_preimage(&mut self, preimage: &[u8], hash_field: &Fr) {
-        self.0
-            .borrow_mut()
-            .update_preimage(&hash_field.to_bytes(), Bytes::copy_from_slice(preimage));
+        // preimage namespace is always writable
+        let _ = self.0.lock().unwrap().update_preimage(
+            TrieDbNamespace::Preimage,
+            &hash_field.to_bytes(),
+            Bytes::copy_from_slice(preimage),
+        );
     }
 
     fn preimage(&self, key: &Fr) -> Vec<u8> {
         self.0
-            .borrow_mut()
-            .get_preimage(&key.to_bytes())
+            .lock()
+            .unwrap()
+            .get(TrieDbNamespace::Preimage, &key.to_bytes())
             .unwrap_or_default()
             .to_vec()
     }
@@ -77,7 +70,7 @@ const MAX_LEVEL: usize = 31 * 8;
 impl<DB: TrieDb> ZkTrieStateDb<DB> {
     pub fn new(storage: DB) -> Self {
         Self {
-            storage: NodeDb(Rc::new(RefCell::new(storage))),
+            storage: NodeDb(Arc::new(Mutex::new(storage))),
             trie: None,
         }
     }
@@ -102,6 +95,25 @@ impl<DB: TrieDb> TrieStorage for ZkTrieStateDb<DB> {
         true
     }
 
+    fn snapshot(&self, root32: &[u8]) -> Option<Self> {
+        let root = Hash::from_bytes(root32);
+        if !root.is_zero()
+            && self
+                .storage
+                .0
+                .lock()
+                .unwrap()
+                .get_node(root.raw_bytes())
+                .is_none()
+        {
+            return None;
+        }
+        Some(Self {
+            storage: self.storage.clone(),
+            trie: Some(ZkTrie::new(MAX_LEVEL, root)),
+        })
+    }
+
     fn compute_root(&self) -> [u8; 32] {
         self.trie
             .as_ref()
@@ -161,12 +173,21 @@ impl<DB: TrieDb> TrieStorage for ZkTrieStateDb<DB> {
         }
     }
 
-    fn get_preimage(&mut self, key: &[u8]) -> Option<Bytes> {
-        self.storage.0.borrow_mut().get_preimage(key)
+    fn get_preimage(&mut self, namespace: TrieDbNamespace, key: &[u8]) -> Option<Bytes> {
+        self.storage.0.lock().unwrap().get_preimage(namespace, key)
     }
 
-    fn update_preimage(&mut self, key: &[u8], value: Bytes) {
-        self.storage.0.borrow_mut().update_preimage(key, value);
+    fn update_preimage(
+        &mut self,
+        namespace: TrieDbNamespace,
+        key: &[u8],
+        value: Bytes,
+    ) -> Result<(), ExitCode> {
+        self.storage
+            .0
+            .lock()
+            .unwrap()
+            .update_preimage(namespace, key, value)
     }
 }
 
@@ -200,7 +221,7 @@ mod tests {
         let root = zkt.compute_root();
         println!("root: {:?}", hex::encode(root));
         // open and read value
-        let zkt2 = ZkTrieStateDb::new_opened(zkt.storage.0.borrow_mut().clone(), &root);
+        let zkt2 = ZkTrieStateDb::new_opened(zkt.storage.0.lock().unwrap().clone(), &root);
         let (data, _flags) = zkt2.get(bytes32!("key1")).unwrap();
         assert_eq!(data[0], *bytes32!("value1"));
         assert_eq!(data[1], *bytes32!("value2"));
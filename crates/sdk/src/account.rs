@@ -21,16 +21,16 @@ use fluentbase_types::{
 };
 use revm_primitives::AccountInfo;
 
-/// Number of fields
-pub const JZKT_ACCOUNT_FIELDS_COUNT: u32 = 6;
-pub const JZKT_STORAGE_FIELDS_COUNT: u32 = 1;
-
-pub const JZKT_ACCOUNT_BALANCE_FIELD: u32 = 0;
-pub const JZKT_ACCOUNT_NONCE_FIELD: u32 = 1;
-pub const JZKT_ACCOUNT_SOURCE_CODE_SIZE_FIELD: u32 = 2;
-pub const JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD: u32 = 3;
-pub const JZKT_ACCOUNT_RWASM_CODE_SIZE_FIELD: u32 = 4;
-pub const JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD: u32 = 5;
+pub use fluentbase_types::{
+    JZKT_ACCOUNT_BALANCE_FIELD,
+    JZKT_ACCOUNT_FIELDS_COUNT,
+    JZKT_ACCOUNT_NONCE_FIELD,
+    JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD,
+    JZKT_ACCOUNT_RWASM_CODE_SIZE_FIELD,
+    JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD,
+    JZKT_ACCOUNT_SOURCE_CODE_SIZE_FIELD,
+    JZKT_STORAGE_FIELDS_COUNT,
+};
 
 /// Compression flags for upper fields.
 ///
@@ -48,6 +48,10 @@ pub type AccountFields = [Bytes32; JZKT_ACCOUNT_FIELDS_COUNT as usize];
 
 pub trait AccountManager {
     fn checkpoint(&self) -> AccountCheckpoint;
+    /// Merges changes of the finished nested frame into the parent frame, so they still can be
+    /// reverted by the parent's rollback.
+    fn checkpoint_merge(&self);
+    /// Commits all changes into the storage, must be called only at the transaction boundary.
     fn commit(&self);
     fn rollback(&self, checkpoint: AccountCheckpoint);
     fn account(&self, address: Address) -> (Account, bool);
@@ -70,7 +74,7 @@ pub trait AccountManager {
     fn precompile(&self, address: &Address, input: &Bytes, gas: u64)
         -> Option<EvmCallMethodOutput>;
     fn is_precompile(&self, address: &Address) -> bool;
-    fn self_destruct(&self, address: Address, target: Address) -> [bool; 4];
+    fn self_destruct(&self, address: Address, target: Address) -> Result<[bool; 4], ExitCode>;
     fn block_hash(&self, number: U256) -> B256;
     fn write_transient_storage(&self, address: Address, index: U256, value: U256);
     fn transient_storage(&self, address: Address, index: U256) -> U256;
@@ -86,6 +90,9 @@ pub struct Account {
     pub source_code_hash: B256,
     pub rwasm_code_size: u64,
     pub rwasm_code_hash: F254,
+    /// Mask of entrypoints exported by the WASM contract (see `entrypoint_mask`), it's packed
+    /// into the rWASM code size field
+    pub entrypoint_mask: u32,
 }
 
 impl Into<AccountInfo> for Account {
@@ -119,6 +126,7 @@ impl From<AccountInfo> for Account {
                 .map(|v| v.len() as u64)
                 .unwrap_or_default(),
             rwasm_code_hash: value.rwasm_code_hash,
+            entrypoint_mask: 0,
         }
     }
 }
@@ -166,6 +174,8 @@ impl Account {
             .copy_from_slice(&fields[JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD as usize]);
         result.rwasm_code_size =
             LittleEndian::read_u64(&fields[JZKT_ACCOUNT_RWASM_CODE_SIZE_FIELD as usize]);
+        result.entrypoint_mask =
+            LittleEndian::read_u32(&fields[JZKT_ACCOUNT_RWASM_CODE_SIZE_FIELD as usize][8..12]);
         result
             .rwasm_code_hash
             .copy_from_slice(&fields[JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD as usize]);
@@ -178,6 +188,10 @@ impl Account {
             &mut account_fields[JZKT_ACCOUNT_RWASM_CODE_SIZE_FIELD as usize][..],
             self.rwasm_code_size,
         );
+        LittleEndian::write_u32(
+            &mut account_fields[JZKT_ACCOUNT_RWASM_CODE_SIZE_FIELD as usize][8..12],
+            self.entrypoint_mask,
+        );
         LittleEndian::write_u64(
             &mut account_fields[JZKT_ACCOUNT_NONCE_FIELD as usize][..],
             self.nonce,
@@ -18,6 +18,7 @@ extern "C" {
         output65_offset: *mut u8,
         rec_id: u32,
     );
+    pub fn _crypto_kzg_point_evaluation(input192_offset: *const u8, output64_offset: *mut u8);
 
     /// Basic system methods that are available for every app (shared and sovereign)
     pub fn _sys_halt(code: i32) -> !;
@@ -73,6 +74,8 @@ extern "C" {
     pub fn _jzkt_rollback(checkpoint: u64);
     pub fn _jzkt_preimage_size(hash32_ptr: *const u8) -> u32;
     pub fn _jzkt_preimage_copy(hash32_ptr: *const u8, preimage_ptr: *mut u8);
+    pub fn _jzkt_transient_get(key32_ptr: *const u8, output32_ptr: *mut u8);
+    pub fn _jzkt_transient_update(key32_ptr: *const u8, value32_ptr: *const u8);
 
     pub fn _wasm_to_rwasm_size(input_ptr: *const u8, input_len: u32) -> i32;
     pub fn _wasm_to_rwasm(
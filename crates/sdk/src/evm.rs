@@ -1,6 +1,7 @@
 use crate::{
     Account,
     AccountCheckpoint,
+    AccountManager,
     LowLevelAPI,
     LowLevelSDK,
     JZKT_ACCOUNT_BALANCE_FIELD,
@@ -15,7 +16,30 @@ use alloc::{vec, vec::Vec};
 use byteorder::{ByteOrder, LittleEndian};
 use fluentbase_codec::{BufferDecoder, Encoder};
 use fluentbase_codec_derive::Codec;
-use fluentbase_types::{Address, Bytes, Bytes32, B256, U256};
+use fluentbase_types::{Address, Bytes, Bytes32, B256, BLOCK_HASH_HISTORY, U256};
+use revm_primitives::SpecId;
+
+/// Selects EVM spec for the block using hardfork schedule of the chain, where each entry is an
+/// EVM spec id with its activation point. Forks before Shanghai are activated by block number
+/// and the later ones by block timestamp (like in the chain config). Empty schedule means Cancun.
+pub fn spec_id_from_hardforks(hardforks: &[(u8, u64)], number: u64, timestamp: u64) -> SpecId {
+    if hardforks.is_empty() {
+        return SpecId::CANCUN;
+    }
+    hardforks
+        .iter()
+        .filter_map(|(spec_id, activation)| {
+            let spec_id = SpecId::try_from_u8(*spec_id)?;
+            let is_active = if spec_id >= SpecId::SHANGHAI {
+                timestamp >= *activation
+            } else {
+                number >= *activation
+            };
+            is_active.then_some(spec_id)
+        })
+        .max()
+        .unwrap_or(SpecId::FRONTIER)
+}
 
 pub trait ContextReader {
     fn journal_checkpoint(&self) -> u64;
@@ -26,6 +50,9 @@ pub trait ContextReader {
     fn block_difficulty(&self) -> u64;
     fn block_gas_limit(&self) -> u64;
     fn block_base_fee(&self) -> U256;
+    fn block_parent_hash(&self) -> B256;
+    fn block_hardforks(&self) -> Vec<(u8, u64)>;
+    fn block_hardforks_size(&self) -> (u32, u32);
     fn tx_gas_limit(&self) -> u64;
     fn tx_nonce(&self) -> u64;
     fn tx_gas_price(&self) -> U256;
@@ -42,6 +69,25 @@ pub trait ContextReader {
     fn contract_is_static(&self) -> bool;
     fn contract_input(&self) -> Bytes;
     fn contract_input_size(&self) -> (u32, u32);
+
+    /// Returns hash of one of the last 256 blocks (zero for other blocks) from the history that
+    /// is kept by the account manager
+    fn block_hash<AM: AccountManager>(&self, am: &AM, number: u64) -> B256 {
+        let block_number = self.block_number();
+        if number >= block_number || block_number - number > BLOCK_HASH_HISTORY {
+            return B256::ZERO;
+        }
+        am.block_hash(U256::from(number))
+    }
+
+    /// Returns EVM spec that is active for the current block
+    fn block_spec_id(&self) -> SpecId {
+        spec_id_from_hardforks(
+            &self.block_hardforks(),
+            self.block_number(),
+            self.block_timestamp(),
+        )
+    }
 }
 
 #[derive(Clone, Debug, Default, Codec)]
@@ -56,6 +102,8 @@ pub struct ContractInput {
     pub block_difficulty: u64,
     pub block_gas_limit: u64,
     pub block_base_fee: U256,
+    pub block_parent_hash: B256,
+    pub block_hardforks: Vec<(u8, u64)>,
     // tx info
     pub tx_gas_limit: u64,
     pub tx_nonce: u64,
@@ -107,6 +155,18 @@ impl ContextReader for ContractInput {
         self.block_base_fee
     }
 
+    fn block_parent_hash(&self) -> B256 {
+        self.block_parent_hash
+    }
+
+    fn block_hardforks(&self) -> Vec<(u8, u64)> {
+        self.block_hardforks.clone()
+    }
+
+    fn block_hardforks_size(&self) -> (u32, u32) {
+        (0, self.block_hardforks.len() as u32 * 9)
+    }
+
     fn tx_gas_limit(&self) -> u64 {
         self.tx_gas_limit
     }
@@ -256,6 +316,8 @@ impl ContextReader for ExecutionContext {
     impl_reader_func!(fn block_difficulty() -> u64, BlockDifficulty);
     impl_reader_func!(fn block_gas_limit() -> u64, BlockGasLimit);
     impl_reader_func!(fn block_base_fee() -> U256, BlockBaseFee);
+    impl_reader_func!(fn block_parent_hash() -> B256, BlockParentHash);
+    impl_reader_func!(@dynamic fn block_hardforks() -> Vec<(u8, u64)>, BlockHardforks);
     // tx info
     impl_reader_func!(fn tx_gas_limit() -> u64, TxGasLimit);
     impl_reader_func!(fn tx_nonce() -> u64, TxNonce);
@@ -299,12 +361,13 @@ impl ExecutionContext {
 #[cfg(test)]
 mod test {
     use crate::{
-        evm::{ContextReader, ContractInput, ExecutionContext},
+        evm::{spec_id_from_hardforks, ContextReader, ContractInput, ExecutionContext},
         LowLevelSDK,
     };
     use fluentbase_codec::{BufferDecoder, Encoder};
     use fluentbase_codec_derive::Codec;
     use fluentbase_types::Bytes;
+    use revm_primitives::SpecId;
 
     #[test]
     fn test_encode_decode() {
@@ -319,4 +382,21 @@ mod test {
         let input = ExecutionContext::default().contract_input();
         assert_eq!(input, contract_input.contract_input);
     }
+
+    #[test]
+    fn test_spec_id_from_hardforks() {
+        let hardforks = [
+            (SpecId::LONDON as u8, 10),
+            (SpecId::MERGE as u8, 20),
+            (SpecId::SHANGHAI as u8, 1_000),
+            (SpecId::CANCUN as u8, 2_000),
+        ];
+        assert_eq!(spec_id_from_hardforks(&[], 0, 0), SpecId::CANCUN);
+        assert_eq!(spec_id_from_hardforks(&hardforks, 5, 0), SpecId::FRONTIER);
+        assert_eq!(spec_id_from_hardforks(&hardforks, 15, 0), SpecId::LONDON);
+        // post-merge forks are activated by timestamp
+        assert_eq!(spec_id_from_hardforks(&hardforks, 25, 999), SpecId::MERGE);
+        assert_eq!(spec_id_from_hardforks(&hardforks, 25, 1_500), SpecId::SHANGHAI);
+        assert_eq!(spec_id_from_hardforks(&hardforks, 25, 2_000), SpecId::CANCUN);
+    }
 }
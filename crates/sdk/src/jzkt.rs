@@ -3,7 +3,10 @@ use crate::{
     Account,
     AccountCheckpoint,
     AccountManager,
+    ContextReader,
+    ContractInput,
     EvmCallMethodOutput,
+    ExecutionContext,
     LowLevelAPI,
     LowLevelSDK,
     JZKT_ACCOUNT_BALANCE_FIELD,
@@ -17,17 +20,148 @@ use crate::{
 };
 use alloc::vec;
 use byteorder::{ByteOrder, LittleEndian};
-use fluentbase_types::{Address, Bytes, Bytes32, ExitCode, B256, U256};
+use fluentbase_codec::Encoder;
+use fluentbase_types::{
+    Address,
+    Bytes,
+    Bytes32,
+    ExitCode,
+    B256,
+    BLOCK_HASH_HISTORY_ADDRESS,
+    BLOCK_HASH_HISTORY_SERVE_WINDOW,
+    POSEIDON_EMPTY,
+    PRECOMPILE_ADDRESSES,
+    STATE_MAIN,
+    U256,
+};
+
+/// Tags of the transient keys used for SELFDESTRUCT bookkeeping. Tag is stored inside the zero
+/// padding of the account key, so these keys can't collide with account or storage keys.
+const SELF_DESTRUCT_STATUS_TAG: u8 = 0x01;
+const SELF_DESTRUCT_SLOT_TAG: u8 = 0x02;
+const SELF_DESTRUCT_TRACKED_TAG: u8 = 0x03;
+
+const ACCOUNT_CREATED_FLAG: u8 = 0x01;
+const ACCOUNT_DESTROYED_FLAG: u8 = 0x02;
+
+#[inline(always)]
+fn self_destruct_key(address: &Address, tag: u8, index: u64) -> [u8; 32] {
+    let mut key = address.into_word().0;
+    key[0] = tag;
+    key[4..12].copy_from_slice(&index.to_le_bytes());
+    key
+}
+
+/// Transient key that marks storage key as already tracked, it's hashed with the tag, so it can't
+/// collide with the storage keys that are used by EIP-1153
+#[inline(always)]
+fn tracked_storage_key(storage_key: &[u8; 32]) -> [u8; 32] {
+    let mut preimage = [0u8; 33];
+    preimage[..32].copy_from_slice(storage_key);
+    preimage[32] = SELF_DESTRUCT_TRACKED_TAG;
+    let mut key = [0u8; 32];
+    LowLevelSDK::crypto_keccak256(preimage.as_ptr(), preimage.len() as u32, key.as_mut_ptr());
+    key
+}
+
+/// Size of the gas used (u64 LE) that is written by precompile contracts before the output
+const PRECOMPILE_GAS_USED_SIZE: usize = 8;
 
 #[derive(Default)]
 pub struct JzktAccountManager;
 
+impl JzktAccountManager {
+    /// Stores hash of the block in the history
+    pub fn write_block_hash(&self, number: u64, hash: B256) {
+        let slot = U256::from(number % BLOCK_HASH_HISTORY_SERVE_WINDOW);
+        self.write_storage(BLOCK_HASH_HISTORY_ADDRESS, slot, U256::from_be_bytes(hash.0));
+    }
+
+    /// Records the parent block hash in the history, it's called at the beginning of each
+    /// transaction, so only the first transaction of the block writes it.
+    pub fn write_parent_block_hash<CR: ContextReader>(&self, cr: &CR) {
+        let (number, hash) = (cr.block_number(), cr.block_parent_hash());
+        if number == 0 || hash == B256::ZERO || self.block_hash(U256::from(number - 1)) == hash {
+            return;
+        }
+        self.write_block_hash(number - 1, hash);
+    }
+
+    /// Pre-warms accounts and slots that are accessed before the transaction execution (EIP-2929),
+    /// it includes caller, target, coinbase (EIP-3651) and the access list (EIP-2930). Precompiles
+    /// don't need it because they are always warm.
+    pub fn warm_up_transaction<CR: ContextReader>(&self, cr: &CR) {
+        self.warm_account(&cr.tx_caller());
+        self.warm_account(&cr.contract_address());
+        self.warm_account(&cr.block_coinbase());
+        for (address, slots) in cr.tx_access_list() {
+            self.warm_account(&address);
+            for slot in slots {
+                self.storage(address, slot, false);
+            }
+        }
+    }
+
+    fn warm_account(&self, address: &Address) {
+        let mut buffer32 = Bytes32::default();
+        LowLevelSDK::jzkt_get(
+            address.into_word().as_ptr(),
+            JZKT_ACCOUNT_NONCE_FIELD,
+            buffer32.as_mut_ptr(),
+            false,
+        );
+    }
+
+    /// Returns account status for the current transaction, where first byte is a set of flags
+    /// and bytes `8..16` is a number of storage keys written since the account creation
+    fn account_status(&self, address: &Address) -> [u8; 32] {
+        let mut status = [0u8; 32];
+        let status_key = self_destruct_key(address, SELF_DESTRUCT_STATUS_TAG, 0);
+        LowLevelSDK::jzkt_transient_get(status_key.as_ptr(), status.as_mut_ptr());
+        status
+    }
+
+    fn write_account_status(&self, address: &Address, status: &[u8; 32]) {
+        let status_key = self_destruct_key(address, SELF_DESTRUCT_STATUS_TAG, 0);
+        LowLevelSDK::jzkt_transient_update(status_key.as_ptr(), status.as_ptr());
+    }
+
+    /// Remembers storage keys of the accounts created in this transaction, we can't enumerate
+    /// storage by the address because keys are hashed
+    fn track_storage_key(&self, address: &Address, storage_key: &[u8; 32]) {
+        let mut status = self.account_status(address);
+        if status[0] & ACCOUNT_CREATED_FLAG == 0 {
+            return;
+        }
+        // repeated writes into the same slot must not grow the list
+        let tracked_key = tracked_storage_key(storage_key);
+        let mut is_tracked = [0u8; 32];
+        LowLevelSDK::jzkt_transient_get(tracked_key.as_ptr(), is_tracked.as_mut_ptr());
+        if is_tracked[0] != 0 {
+            return;
+        }
+        is_tracked[0] = 1;
+        LowLevelSDK::jzkt_transient_update(tracked_key.as_ptr(), is_tracked.as_ptr());
+        let count = LittleEndian::read_u64(&status[8..16]);
+        let slot_key = self_destruct_key(address, SELF_DESTRUCT_SLOT_TAG, count);
+        LowLevelSDK::jzkt_transient_update(slot_key.as_ptr(), storage_key.as_ptr());
+        LittleEndian::write_u64(&mut status[8..16], count + 1);
+        self.write_account_status(address, &status);
+    }
+}
+
 impl AccountManager for JzktAccountManager {
     #[inline(always)]
     fn checkpoint(&self) -> AccountCheckpoint {
         LowLevelSDK::jzkt_checkpoint()
     }
 
+    #[inline(always)]
+    fn checkpoint_merge(&self) {
+        // changes are already in the journal after the parent's checkpoint, so there is nothing
+        // to do here, parent can still roll them back
+    }
+
     #[inline(always)]
     fn commit(&self) {
         let mut root32: [u8; 32] = [0u8; 32];
@@ -45,7 +179,8 @@ impl AccountManager for JzktAccountManager {
         let address_word = address.into_word();
         // code size and nonce
         let mut buffer32 = Bytes32::default();
-        LowLevelSDK::jzkt_get(
+        // only first read can be cold, the rest of fields are warm already
+        let is_cold = LowLevelSDK::jzkt_get(
             address_word.as_ptr(),
             JZKT_ACCOUNT_NONCE_FIELD,
             buffer32.as_mut_ptr(),
@@ -65,6 +200,7 @@ impl AccountManager for JzktAccountManager {
             false,
         );
         result.rwasm_code_size = LittleEndian::read_u64(&buffer32);
+        result.entrypoint_mask = LittleEndian::read_u32(&buffer32[8..12]);
         LowLevelSDK::jzkt_get(
             address_word.as_ptr(),
             JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD,
@@ -84,7 +220,9 @@ impl AccountManager for JzktAccountManager {
             result.source_code_hash.as_mut_ptr(),
             false,
         );
-        (result, true)
+        // precompiles are always warm (EIP-2929)
+        let is_cold = is_cold && !self.is_precompile(&address);
+        (result, is_cold)
     }
 
     #[inline(always)]
@@ -145,6 +283,7 @@ impl AccountManager for JzktAccountManager {
             value.as_le_slice().as_ptr() as *const [u8; 32],
             32,
         );
+        self.track_storage_key(&address, &storage_key);
         true
     }
 
@@ -196,34 +335,292 @@ impl AccountManager for JzktAccountManager {
 
     fn precompile(
         &self,
-        _address: &Address,
-        _input: &Bytes,
-        _gas: u64,
+        address: &Address,
+        input: &Bytes,
+        gas: u64,
     ) -> Option<EvmCallMethodOutput> {
-        // in jzkt mode we don't support precompiles
-        None
+        if !self.is_precompile(address) {
+            return None;
+        }
+        let (account, _) = self.account(*address);
+        if account.rwasm_code_hash == POSEIDON_EMPTY || account.rwasm_code_hash == B256::ZERO {
+            return Some(EvmCallMethodOutput::from_exit_code(ExitCode::PrecompileError));
+        }
+        let contract_input = ContractInput {
+            contract_gas_limit: gas,
+            contract_address: *address,
+            contract_input: input.clone(),
+            // blob hashes are required by the point evaluation precompile
+            tx_blob_hashes: ExecutionContext::DEFAULT.tx_blob_hashes(),
+            ..Default::default()
+        };
+        // execution is bounded by the call gas, the precompile gas used is reported in the output
+        let mut fuel = gas.min(u32::MAX as u64) as u32;
+        let (output, exit_code) = self.exec_hash(
+            account.rwasm_code_hash.as_ptr(),
+            &contract_input.encode_to_vec(0),
+            &mut fuel as *mut u32,
+            STATE_MAIN,
+        );
+        // failed precompile consumes all gas like in EVM
+        if !ExitCode::from(exit_code).is_ok() {
+            return Some(EvmCallMethodOutput::from_exit_code(ExitCode::from(exit_code)));
+        }
+        if output.len() < PRECOMPILE_GAS_USED_SIZE {
+            return Some(EvmCallMethodOutput::from_exit_code(ExitCode::PrecompileError));
+        }
+        let gas_used = LittleEndian::read_u64(&output[..PRECOMPILE_GAS_USED_SIZE]);
+        if gas_used > gas {
+            return Some(EvmCallMethodOutput::from_exit_code(ExitCode::OutOfFuel));
+        }
+        Some(
+            EvmCallMethodOutput::from_exit_code(ExitCode::Ok)
+                .with_output(output.slice(PRECOMPILE_GAS_USED_SIZE..))
+                .with_gas(gas - gas_used, 0),
+        )
+    }
+
+    fn is_precompile(&self, address: &Address) -> bool {
+        PRECOMPILE_ADDRESSES.contains(address)
+    }
+
+    fn self_destruct(
+        &self,
+        address: Address,
+        target: Address,
+    ) -> Result<[bool; 4], ExitCode> {
+        let (mut account, _) = self.account(address);
+        let (mut target_account, is_cold) = self.account(target);
+        let had_value = account.balance != U256::ZERO;
+        let target_exists = target_account.is_not_empty();
+        let mut status = self.account_status(&address);
+        let previously_destroyed = status[0] & ACCOUNT_DESTROYED_FLAG != 0;
+        // move all balance to the target (for the same target balance is burned only on removal)
+        if address != target {
+            let balance = account.balance;
+            Account::transfer(&mut account, &mut target_account, balance)?;
+            self.write_account(&account);
+            self.write_account(&target_account);
+        }
+        // since EIP-6780 account is removed only if it was created in the same transaction
+        if status[0] & ACCOUNT_CREATED_FLAG != 0 {
+            let count = LittleEndian::read_u64(&status[8..16]);
+            let mut storage_key = [0u8; 32];
+            for index in 0..count {
+                let slot_key = self_destruct_key(&address, SELF_DESTRUCT_SLOT_TAG, index);
+                LowLevelSDK::jzkt_transient_get(slot_key.as_ptr(), storage_key.as_mut_ptr());
+                LowLevelSDK::jzkt_remove(storage_key.as_ptr());
+            }
+            LowLevelSDK::jzkt_remove(address.into_word().as_ptr());
+            status[0] |= ACCOUNT_DESTROYED_FLAG;
+            self.write_account_status(&address, &status);
+        }
+        Ok([had_value, target_exists, is_cold, previously_destroyed])
+    }
+
+    fn block_hash(&self, number: U256) -> B256 {
+        // range is checked by the caller, history keeps only the last window of blocks
+        let slot = number % U256::from(BLOCK_HASH_HISTORY_SERVE_WINDOW);
+        let (value, _) = self.storage(BLOCK_HASH_HISTORY_ADDRESS, slot, false);
+        B256::from(value.to_be_bytes::<32>())
+    }
+
+    #[inline(always)]
+    fn write_transient_storage(&self, address: Address, index: U256, value: U256) {
+        let storage_key = calc_storage_key(&address, index.as_le_slice().as_ptr());
+        LowLevelSDK::jzkt_transient_update(storage_key.as_ptr(), value.as_le_slice().as_ptr());
     }
 
-    fn is_precompile(&self, _address: &Address) -> bool {
-        // in jzkt mode we don't support precompiles
-        false
+    #[inline(always)]
+    fn transient_storage(&self, address: Address, index: U256) -> U256 {
+        let mut value = U256::ZERO;
+        let storage_key = calc_storage_key(&address, index.as_le_slice().as_ptr());
+        LowLevelSDK::jzkt_transient_get(storage_key.as_ptr(), unsafe {
+            value.as_le_slice_mut().as_mut_ptr()
+        });
+        value
+    }
+
+    fn mark_account_created(&self, address: Address) {
+        let mut status = self.account_status(&address);
+        status[0] |= ACCOUNT_CREATED_FLAG;
+        self.write_account_status(&address, &status);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Account, AccountManager, ContextReader, ContractInput, JzktAccountManager};
+    use alloc::vec;
+    use byteorder::{ByteOrder, LittleEndian};
+    use fluentbase_types::{
+        Address,
+        Bytes,
+        ExitCode,
+        B256,
+        BLOCK_HASH_HISTORY_SERVE_WINDOW,
+        U256,
+    };
+
+    #[test]
+    fn test_merged_frame_can_be_reverted_by_parent() {
+        let am = JzktAccountManager::default();
+        let address = Address::with_last_byte(1);
+        let parent_checkpoint = am.checkpoint();
+        // nested frame succeeds and merges its changes into the parent frame
+        let _nested_checkpoint = am.checkpoint();
+        am.write_storage(address, U256::from(1), U256::from(100));
+        am.checkpoint_merge();
+        assert_eq!(am.storage(address, U256::from(1), false).0, U256::from(100));
+        // parent frame fails, so nested changes must be reverted too
+        am.rollback(parent_checkpoint);
+        assert_eq!(am.storage(address, U256::from(1), false).0, U256::ZERO);
     }
 
-    fn self_destruct(&self, _address: Address, _target: Address) -> [bool; 4] {
-        todo!("how we can support SELFDESTRUCT (?)")
+    #[test]
+    fn test_transaction_warm_up() {
+        let am = JzktAccountManager::default();
+        let caller = Address::with_last_byte(0x21);
+        let listed = Address::with_last_byte(0x22);
+        let cr = ContractInput {
+            tx_caller: caller,
+            tx_access_list: vec![(listed, vec![U256::from(1)])],
+            ..Default::default()
+        };
+        am.warm_up_transaction(&cr);
+        assert!(!am.account(caller).1);
+        assert!(!am.account(listed).1);
+        assert!(!am.storage(listed, U256::from(1), false).1);
+        // keys outside of the access list are cold on the first access only
+        assert!(am.storage(listed, U256::from(2), false).1);
+        assert!(!am.storage(listed, U256::from(2), false).1);
+        assert!(am.account(Address::with_last_byte(0x23)).1);
     }
 
-    fn block_hash(&self, _number: U256) -> B256 {
-        todo!("how we can support BLOCKHASH (?)")
+    #[test]
+    fn test_transient_storage_is_reverted_with_frame() {
+        let am = JzktAccountManager::default();
+        let address = Address::with_last_byte(2);
+        am.write_transient_storage(address, U256::from(1), U256::from(7));
+        let checkpoint = am.checkpoint();
+        am.write_transient_storage(address, U256::from(1), U256::from(8));
+        assert_eq!(am.transient_storage(address, U256::from(1)), U256::from(8));
+        am.rollback(checkpoint);
+        assert_eq!(am.transient_storage(address, U256::from(1)), U256::from(7));
+        // transient slots never leak into the persistent storage
+        assert_eq!(am.storage(address, U256::from(1), false).0, U256::ZERO);
     }
 
-    fn write_transient_storage(&self, _address: Address, _index: U256, _value: U256) {
-        todo!("how we can support TLOAD (?)")
+    #[test]
+    fn test_self_destruct_removes_only_created_accounts() {
+        let am = JzktAccountManager::default();
+        let target = Address::with_last_byte(0xff);
+        for (address, is_created) in [
+            (Address::with_last_byte(3), false),
+            (Address::with_last_byte(4), true),
+        ] {
+            let mut account = Account::new(address);
+            account.balance = U256::from(10);
+            account.nonce = 1;
+            am.write_account(&account);
+            if is_created {
+                am.mark_account_created(address);
+            }
+            am.write_storage(address, U256::from(1), U256::from(100));
+            let [had_value, _, _, previously_destroyed] = am.self_destruct(address, target).unwrap();
+            assert!(had_value);
+            assert!(!previously_destroyed);
+            // balance is always moved to the target
+            assert_eq!(am.account(address).0.balance, U256::ZERO);
+            let expected_storage = if is_created { U256::ZERO } else { U256::from(100) };
+            assert_eq!(am.storage(address, U256::from(1), false).0, expected_storage);
+            assert_eq!(am.account(address).0.nonce, if is_created { 0 } else { 1 });
+            assert_eq!(am.self_destruct(address, target).unwrap()[3], is_created);
+        }
+        assert_eq!(am.account(target).0.balance, U256::from(20));
     }
 
-    fn transient_storage(&self, _address: Address, _index: U256) -> U256 {
-        todo!("how we can support TSTORE (?)")
+    #[test]
+    fn test_self_destruct_balance_overflow_is_an_error() {
+        let am = JzktAccountManager::default();
+        let (address, target) = (Address::with_last_byte(5), Address::with_last_byte(6));
+        let mut account = Account::new(address);
+        account.balance = U256::from(1);
+        am.write_account(&account);
+        let mut target_account = Account::new(target);
+        target_account.balance = U256::MAX;
+        am.write_account(&target_account);
+        assert_eq!(am.self_destruct(address, target), Err(ExitCode::OverflowPayment));
+        assert_eq!(am.account(address).0.balance, U256::from(1));
     }
 
-    fn mark_account_created(&self, _address: Address) {}
+    #[test]
+    fn test_entrypoint_mask_is_kept_next_to_rwasm_code_size() {
+        let am = JzktAccountManager::default();
+        let address = Address::with_last_byte(7);
+        let mut account = Account::new(address);
+        account.rwasm_code_size = 100;
+        account.entrypoint_mask = 0b110;
+        am.write_account(&account);
+        let (account, _) = am.account(address);
+        assert_eq!(account.rwasm_code_size, 100);
+        assert_eq!(account.entrypoint_mask, 0b110);
+    }
+
+    #[test]
+    fn test_storage_keys_are_tracked_once() {
+        let am = JzktAccountManager::default();
+        let address = Address::with_last_byte(5);
+        am.mark_account_created(address);
+        for slot in [1, 1, 2, 1, 2] {
+            am.write_storage(address, U256::from(slot), U256::from(100));
+        }
+        let status = am.account_status(&address);
+        assert_eq!(LittleEndian::read_u64(&status[8..16]), 2);
+    }
+
+    #[test]
+    fn test_precompiles_are_dispatched_by_address() {
+        let am = JzktAccountManager::default();
+        assert!(am.is_precompile(&Address::with_last_byte(1)));
+        assert!(!am.is_precompile(&Address::with_last_byte(0x0b)));
+        assert!(am
+            .precompile(&Address::with_last_byte(0x0b), &Bytes::new(), 1_000)
+            .is_none());
+        // precompile address without deployed contract must fail instead of an empty call
+        let output = am
+            .precompile(&Address::with_last_byte(2), &Bytes::new(), 1_000)
+            .unwrap();
+        assert_eq!(output.exit_code, ExitCode::PrecompileError.into_i32());
+        assert!(!am.account(Address::with_last_byte(2)).1);
+    }
+
+    #[test]
+    fn test_block_hash_history() {
+        let am = JzktAccountManager::default();
+        let hash = B256::repeat_byte(0xaa);
+        am.write_block_hash(100, hash);
+        assert_eq!(am.block_hash(U256::from(100)), hash);
+        assert_eq!(am.block_hash(U256::from(101)), B256::ZERO);
+        // history is a ring buffer, so old hashes are overwritten
+        let hash2 = B256::repeat_byte(0xbb);
+        am.write_block_hash(100 + BLOCK_HASH_HISTORY_SERVE_WINDOW, hash2);
+        assert_eq!(am.block_hash(U256::from(100)), hash2);
+    }
+
+    #[test]
+    fn test_parent_block_hash_is_recorded() {
+        let am = JzktAccountManager::default();
+        let cr = ContractInput {
+            block_number: 200,
+            block_parent_hash: B256::repeat_byte(0xcc),
+            ..Default::default()
+        };
+        am.write_parent_block_hash(&cr);
+        assert_eq!(cr.block_hash(&am, 199), B256::repeat_byte(0xcc));
+        // current block and blocks out of the window are not available
+        assert_eq!(cr.block_hash(&am, 200), B256::ZERO);
+        am.write_block_hash(10, B256::repeat_byte(0xdd));
+        assert_eq!(cr.block_hash(&am, 10), B256::ZERO);
+    }
 }
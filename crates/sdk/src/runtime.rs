@@ -3,6 +3,7 @@ use byteorder::{ByteOrder, LittleEndian};
 use fluentbase_runtime::{
     instruction::{
         crypto_ecrecover::CryptoEcrecover,
+        crypto_kzg_point_evaluation::CryptoKzgPointEvaluation,
         crypto_keccak256::CryptoKeccak256,
         crypto_poseidon::CryptoPoseidon,
         crypto_poseidon2::CryptoPoseidon2,
@@ -17,6 +18,8 @@ use fluentbase_runtime::{
         jzkt_preimage_size::JzktPreimageSize,
         jzkt_remove::JzktRemove,
         jzkt_rollback::JzktRollback,
+        jzkt_transient_get::JzktTransientGet,
+        jzkt_transient_update::JzktTransientUpdate,
         jzkt_update::JzktUpdate,
         jzkt_update_preimage::JzktUpdatePreimage,
         sys_exec_hash::SysExecHash,
@@ -37,7 +40,15 @@ use fluentbase_runtime::{
     DefaultEmptyRuntimeDatabase,
     RuntimeContext,
 };
-use fluentbase_types::{Address, Bytes, ExitCode, JournalCheckpoint, B256};
+use fluentbase_types::{
+    Address,
+    Bytes,
+    ExitCode,
+    IJournaledTrie,
+    JournalCheckpoint,
+    B256,
+    JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD,
+};
 use std::ptr;
 
 type Context = RuntimeContext<DefaultEmptyRuntimeDatabase>;
@@ -120,6 +131,15 @@ impl LowLevelAPI for LowLevelSDK {
         output.copy_from_slice(&result);
     }
 
+    fn crypto_kzg_point_evaluation(input192_ptr: *const u8, output64_ptr: *mut u8) {
+        let input = unsafe { &*ptr::slice_from_raw_parts(input192_ptr, 192) };
+        let output = unsafe { &mut *ptr::slice_from_raw_parts_mut(output64_ptr, 64) };
+        match CryptoKzgPointEvaluation::fn_impl(input) {
+            Ok(result) => output.copy_from_slice(&result),
+            Err(exit_code) => Self::sys_halt(exit_code.into_i32()),
+        }
+    }
+
     fn sys_read(target: &mut [u8], offset: u32) {
         let result =
             with_context(|ctx| SysRead::fn_impl(ctx, offset, target.len() as u32).unwrap());
@@ -213,13 +233,10 @@ impl LowLevelAPI for LowLevelSDK {
         committed: bool,
     ) -> bool {
         let key = unsafe { &*ptr::slice_from_raw_parts(key32_offset, 32) };
-        match with_context_mut(|ctx| JzktGet::fn_impl(ctx, key, field, committed)) {
-            Some((output, is_cold)) => {
-                unsafe { ptr::copy(output.as_ptr(), output32_offset, 32) }
-                is_cold
-            }
-            None => true,
-        }
+        let (output, is_cold) =
+            with_context_mut(|ctx| JzktGet::fn_impl(ctx, key, field, committed));
+        unsafe { ptr::copy(output.as_ptr(), output32_offset, 32) }
+        is_cold
     }
     fn jzkt_update(key32_ptr: *const u8, flags: u32, vals32_ptr: *const [u8; 32], vals32_len: u32) {
         let key = unsafe { &*ptr::slice_from_raw_parts(key32_ptr, 32) };
@@ -266,6 +283,7 @@ impl LowLevelAPI for LowLevelSDK {
                 topics,
                 Bytes::copy_from_slice(data),
             )
+            .unwrap()
         });
     }
     fn jzkt_commit(root32_offset: *mut u8) {
@@ -273,19 +291,35 @@ impl LowLevelAPI for LowLevelSDK {
         unsafe { ptr::copy(root.as_ptr(), root32_offset, 32) }
     }
     fn jzkt_rollback(checkpoint: u64) {
-        with_context_mut(|ctx| JzktRollback::fn_impl(ctx, JournalCheckpoint::from_u64(checkpoint)));
+        with_context_mut(|ctx| {
+            JzktRollback::fn_impl(ctx, JournalCheckpoint::from_u64(checkpoint)).unwrap()
+        });
     }
     fn jzkt_preimage_size(key32_ptr: *const u8) -> u32 {
         let key = unsafe { &*ptr::slice_from_raw_parts(key32_ptr, 32) };
-        return with_context_mut(|ctx| JzktPreimageSize::fn_impl(ctx, key).unwrap());
+        return with_context_mut(|ctx| {
+            JzktPreimageSize::fn_impl(ctx, JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD, key).unwrap()
+        });
     }
     fn jzkt_preimage_copy(key32_ptr: *const u8, preimage_ptr: *mut u8) {
         let key = unsafe { &*ptr::slice_from_raw_parts(key32_ptr, 32) };
-        let preimage_copy = with_context_mut(|ctx| JzktPreimageCopy::fn_impl(ctx, key).unwrap());
+        let preimage_copy = with_context_mut(|ctx| {
+            JzktPreimageCopy::fn_impl(ctx, JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD, key).unwrap()
+        });
         let dest =
             unsafe { &mut *ptr::slice_from_raw_parts_mut(preimage_ptr, preimage_copy.len()) };
         dest.copy_from_slice(&preimage_copy);
     }
+    fn jzkt_transient_get(key32_ptr: *const u8, output32_ptr: *mut u8) {
+        let key = unsafe { &*ptr::slice_from_raw_parts(key32_ptr, 32) };
+        let value = with_context_mut(|ctx| JzktTransientGet::fn_impl(ctx, key));
+        unsafe { ptr::copy(value.as_ptr(), output32_ptr, 32) }
+    }
+    fn jzkt_transient_update(key32_ptr: *const u8, value32_ptr: *const u8) {
+        let key = unsafe { &*ptr::slice_from_raw_parts(key32_ptr, 32) };
+        let value = unsafe { &*ptr::slice_from_raw_parts(value32_ptr, 32) };
+        with_context_mut(|ctx| JzktTransientUpdate::fn_impl(ctx, key, value).unwrap());
+    }
     fn wasm_to_rwasm_size(input_ptr: *const u8, input_len: u32) -> i32 {
         let wasm_binary = unsafe { &*ptr::slice_from_raw_parts(input_ptr, input_len as usize) };
         with_context_mut(|ctx| WasmToRwasmSize::fn_impl(ctx, wasm_binary).map_err(|v| v.into_i32()))
@@ -325,6 +359,15 @@ impl LowLevelSDK {
         });
     }
 
+    pub fn with_test_is_static(is_static: bool) {
+        with_context_mut(|ctx| {
+            let static_checkpoint = is_static.then(|| ctx.jzkt().checkpoint().to_u64());
+            *ctx = std::mem::take(ctx)
+                .with_is_static(is_static)
+                .with_static_checkpoint(static_checkpoint);
+        });
+    }
+
     pub fn get_test_output() -> Vec<u8> {
         with_context_mut(|ctx| {
             let output = ctx.output().clone();
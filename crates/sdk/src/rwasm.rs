@@ -1,6 +1,7 @@
 use crate::{
     bindings::{
         _crypto_ecrecover,
+        _crypto_kzg_point_evaluation,
         _crypto_keccak256,
         _crypto_poseidon,
         _crypto_poseidon2,
@@ -15,6 +16,8 @@ use crate::{
         _jzkt_preimage_size,
         _jzkt_remove,
         _jzkt_rollback,
+        _jzkt_transient_get,
+        _jzkt_transient_update,
         _jzkt_update,
         _jzkt_update_preimage,
         _sys_exec_hash,
@@ -133,6 +136,11 @@ impl LowLevelAPI for LowLevelSDK {
         unsafe { _crypto_ecrecover(digest32_ptr, sig64_ptr, output65_ptr, rec_id as u32) }
     }
 
+    #[inline(always)]
+    fn crypto_kzg_point_evaluation(input192_ptr: *const u8, output64_ptr: *mut u8) {
+        unsafe { _crypto_kzg_point_evaluation(input192_ptr, output64_ptr) }
+    }
+
     #[inline(always)]
     fn jzkt_open(root32_ptr: *const u8) {
         unsafe { _jzkt_open(root32_ptr) }
@@ -208,6 +216,14 @@ impl LowLevelAPI for LowLevelSDK {
         unsafe { _jzkt_preimage_copy(hash32_ptr, preimage_ptr) }
     }
     #[inline(always)]
+    fn jzkt_transient_get(key32_ptr: *const u8, output32_ptr: *mut u8) {
+        unsafe { _jzkt_transient_get(key32_ptr, output32_ptr) }
+    }
+    #[inline(always)]
+    fn jzkt_transient_update(key32_ptr: *const u8, value32_ptr: *const u8) {
+        unsafe { _jzkt_transient_update(key32_ptr, value32_ptr) }
+    }
+    #[inline(always)]
     fn wasm_to_rwasm_size(input_ptr: *const u8, input_len: u32) -> i32 {
         unsafe { _wasm_to_rwasm_size(input_ptr, input_len) }
     }
@@ -13,6 +13,7 @@ pub trait LowLevelAPI {
         output65_ptr: *mut u8,
         rec_id: u8,
     );
+    fn crypto_kzg_point_evaluation(input192_ptr: *const u8, output64_ptr: *mut u8);
 
     fn sys_read(target: &mut [u8], offset: u32);
     fn sys_input_size() -> u32;
@@ -62,6 +63,8 @@ pub trait LowLevelAPI {
     fn jzkt_rollback(checkpoint: u64);
     fn jzkt_preimage_size(hash32_ptr: *const u8) -> u32;
     fn jzkt_preimage_copy(hash32_ptr: *const u8, preimage_ptr: *mut u8);
+    fn jzkt_transient_get(key32_ptr: *const u8, output32_ptr: *mut u8);
+    fn jzkt_transient_update(key32_ptr: *const u8, value32_ptr: *const u8);
     fn wasm_to_rwasm_size(input_ptr: *const u8, input_len: u32) -> i32;
     fn wasm_to_rwasm(
         input_ptr: *const u8,
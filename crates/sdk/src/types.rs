@@ -79,6 +79,8 @@ pub struct EvmCallMethodInput {
     pub input: Bytes,
     pub gas_limit: u64,
     pub depth: u32,
+    /// Entrypoint of WASM callee (one of `STATE_*` constants), it's ignored for EVM callees
+    pub state: u32,
 }
 
 #[derive(Default, Debug, Clone, Codec)]
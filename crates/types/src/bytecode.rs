@@ -1,23 +1,132 @@
+use crate::{Address, Bytes, U256};
+
 #[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BytecodeType {
     EVM,
+    EOF,
     WASM,
 }
 
-const WASM_SIG: [u8; 4] = [0x00, 0x61, 0x73, 0x6d];
-const RWASM_SIG: [u8; 2] = [0xef, 0x00];
+/// WebAssembly binary magic (`\0asm`)
+pub const WASM_MAGIC: [u8; 4] = [0x00, 0x61, 0x73, 0x6d];
+/// rWASM binaries start with `0xEF00` followed by `R` (0x52), so they never collide with EOF
+pub const RWASM_MAGIC: [u8; 3] = [0xef, 0x00, 0x52];
+/// EIP-3540 magic of EVM Object Format container
+pub const EOF_MAGIC: [u8; 2] = [0xef, 0x00];
+/// The only supported EOF container version
+pub const EOF_VERSION: u8 = 0x01;
 
 impl BytecodeType {
     pub fn from_slice(input: &[u8]) -> Self {
         // default WebAssembly signature (\0ASM)
-        if input.len() >= 4 && input[0..4] == WASM_SIG {
+        if input.starts_with(&WASM_MAGIC) {
             return Self::WASM;
         }
         // case for rWASM contracts that are inside genesis
-        if input.len() >= 2 && input[0..2] == RWASM_SIG {
+        if input.starts_with(&RWASM_MAGIC) {
+            return Self::WASM;
+        }
+        // EOF container must have both magic and version, the rest is checked by validation
+        if input.starts_with(&EOF_MAGIC) && input.get(2) == Some(&EOF_VERSION) {
+            return Self::EOF;
+        }
+        // WASM module with constructor arguments in front of it
+        if split_prefixed_wasm_initcode(input).is_some() {
             return Self::WASM;
         }
         // all the rest are EVM bytecode
         Self::EVM
     }
 }
+
+/// Splits WASM deploy payload in the `args_len (u32 LE) | args | module` form into the module and
+/// constructor arguments, returns `None` if the input doesn't have this form
+pub fn split_prefixed_wasm_initcode(input: &[u8]) -> Option<(&[u8], &[u8])> {
+    let args_len = u32::from_le_bytes(input.get(..4)?.try_into().unwrap()) as usize;
+    if args_len > input.len() - 4 {
+        return None;
+    }
+    let (args, module) = input[4..].split_at(args_len);
+    if !module.starts_with(&WASM_MAGIC) {
+        return None;
+    }
+    Some((module, args))
+}
+
+/// Max length of the bytecode magic prefix that can be registered for a VM loader
+pub const VM_LOADER_MAGIC_MAX_LEN: usize = 11;
+/// Max number of VM loaders that can be registered in the VM registry
+pub const VM_REGISTRY_MAX_ENTRIES: u64 = 16;
+
+/// VM loader that executes all bytecode starting with the magic prefix by calling its system
+/// contract with `_evm_call`/`_evm_create` method inputs.
+///
+/// Loaders are stored in the VM registry storage, where slot `0` keeps the number of loaders and
+/// slot `i + 1` keeps i-th loader packed into a single word.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VmLoaderEntry {
+    pub magic: Bytes,
+    pub address: Address,
+}
+
+impl VmLoaderEntry {
+    pub fn new(magic: &[u8], address: Address) -> Self {
+        assert!(
+            !magic.is_empty() && magic.len() <= VM_LOADER_MAGIC_MAX_LEN,
+            "magic length must be between 1 and {} bytes",
+            VM_LOADER_MAGIC_MAX_LEN
+        );
+        Self {
+            magic: Bytes::copy_from_slice(magic),
+            address,
+        }
+    }
+
+    /// Packs magic length, magic and system contract address into a storage word
+    pub fn to_storage_value(&self) -> U256 {
+        let mut word = [0u8; 32];
+        word[0] = self.magic.len() as u8;
+        word[1..1 + self.magic.len()].copy_from_slice(&self.magic);
+        word[12..].copy_from_slice(self.address.as_slice());
+        U256::from_be_bytes(word)
+    }
+
+    pub fn from_storage_value(value: U256) -> Option<Self> {
+        let word = value.to_be_bytes::<32>();
+        let magic_len = word[0] as usize;
+        if magic_len == 0 || magic_len > VM_LOADER_MAGIC_MAX_LEN {
+            return None;
+        }
+        Some(Self::new(&word[1..1 + magic_len], Address::from_slice(&word[12..])))
+    }
+
+    pub fn matches(&self, bytecode: &[u8]) -> bool {
+        bytecode.starts_with(&self.magic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_prefixed_wasm_initcode, BytecodeType, WASM_MAGIC};
+
+    #[test]
+    fn test_bytecode_type_from_slice() {
+        assert_eq!(BytecodeType::from_slice(&[0x00, 0x61, 0x73, 0x6d]), BytecodeType::WASM);
+        assert_eq!(BytecodeType::from_slice(&[0xef, 0x00, 0x52, 0x01]), BytecodeType::WASM);
+        assert_eq!(BytecodeType::from_slice(&[0xef, 0x00, 0x01, 0x01]), BytecodeType::EOF);
+        // unknown EOF version and plain legacy code are EVM
+        assert_eq!(BytecodeType::from_slice(&[0xef, 0x00, 0x02]), BytecodeType::EVM);
+        assert_eq!(BytecodeType::from_slice(&[0xef, 0x00]), BytecodeType::EVM);
+        assert_eq!(BytecodeType::from_slice(&[0x60, 0x00]), BytecodeType::EVM);
+        // WASM module prefixed with constructor arguments
+        let payload = [&[2, 0, 0, 0, 0xaa, 0xbb][..], &WASM_MAGIC].concat();
+        assert_eq!(BytecodeType::from_slice(&payload), BytecodeType::WASM);
+        assert_eq!(
+            split_prefixed_wasm_initcode(&payload),
+            Some((&WASM_MAGIC[..], &[0xaa, 0xbb][..]))
+        );
+        assert_eq!(BytecodeType::from_slice(&payload[..payload.len() - 1]), BytecodeType::EVM);
+        assert_eq!(BytecodeType::from_slice(&[0xff, 0xff, 0xff, 0xff]), BytecodeType::EVM);
+    }
+}
@@ -3,7 +3,12 @@ use crate::{
     ExitCode,
     SysFuncIdx::SYS_STATE,
     STATE_DEPLOY,
+    STATE_FALLBACK,
     STATE_MAIN,
+    STATE_RECEIVE,
+    STATE_UPGRADE,
+    STATE_VIEW,
+    U256,
 };
 use alloc::{boxed::Box, string::ToString, vec, vec::Vec};
 use rwasm::{
@@ -11,3 +16,79 @@ use rwasm::{
     rwasm::{BinaryFormat, BinaryFormatWriter, RwasmModule},
     Error,
 };
+
+/// Exported functions that rWASM state router dispatches by the state id
+pub const STATE_ROUTER_ENTRYPOINTS: [(&str, u32); 6] = [
+    ("deploy", STATE_DEPLOY),
+    ("main", STATE_MAIN),
+    ("view", STATE_VIEW),
+    ("receive", STATE_RECEIVE),
+    ("fallback", STATE_FALLBACK),
+    ("upgrade", STATE_UPGRADE),
+];
+
+/// Returns the bit of the state in the mask of exported entrypoints
+pub const fn entrypoint_mask(state: u32) -> u32 {
+    1 << state
+}
+
+/// Selects entrypoint by the call shape, plain value transfers with empty input go to `receive`
+pub fn call_entrypoint_state(input: &[u8], value: &U256) -> u32 {
+    if input.is_empty() && !value.is_zero() {
+        STATE_RECEIVE
+    } else {
+        STATE_MAIN
+    }
+}
+
+/// Routes the state to the entrypoint exported by the contract, if it's missing then `fallback`
+/// is used and `main` otherwise. Empty mask means entrypoints weren't recorded at deployment,
+/// so only required `deploy` and `main` are assumed.
+pub fn resolve_entrypoint_state(state: u32, exported_mask: u32) -> u32 {
+    let exported_mask = if exported_mask == 0 {
+        entrypoint_mask(STATE_DEPLOY) | entrypoint_mask(STATE_MAIN)
+    } else {
+        exported_mask
+    };
+    if state < u32::BITS && exported_mask & entrypoint_mask(state) != 0 {
+        state
+    } else if exported_mask & entrypoint_mask(STATE_FALLBACK) != 0 {
+        STATE_FALLBACK
+    } else {
+        STATE_MAIN
+    }
+}
+
+/// Creates state router config for all supported entrypoints, missing exports are skipped
+pub fn create_state_router_config() -> StateRouterConfig {
+    StateRouterConfig {
+        states: STATE_ROUTER_ENTRYPOINTS
+            .iter()
+            .map(|(name, state)| (name.to_string(), *state))
+            .collect(),
+        opcode: Instruction::Call(SYS_STATE.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{call_entrypoint_state, entrypoint_mask, resolve_entrypoint_state};
+    use crate::{STATE_DEPLOY, STATE_FALLBACK, STATE_MAIN, STATE_RECEIVE, STATE_VIEW, U256};
+
+    #[test]
+    fn test_entrypoint_state_routing() {
+        assert_eq!(call_entrypoint_state(&[], &U256::from(1)), STATE_RECEIVE);
+        assert_eq!(call_entrypoint_state(&[], &U256::ZERO), STATE_MAIN);
+        assert_eq!(call_entrypoint_state(&[0x01], &U256::from(1)), STATE_MAIN);
+        let required = entrypoint_mask(STATE_DEPLOY) | entrypoint_mask(STATE_MAIN);
+        let with_receive = required | entrypoint_mask(STATE_RECEIVE);
+        let with_fallback = required | entrypoint_mask(STATE_FALLBACK);
+        assert_eq!(resolve_entrypoint_state(STATE_RECEIVE, with_receive), STATE_RECEIVE);
+        assert_eq!(resolve_entrypoint_state(STATE_RECEIVE, with_fallback), STATE_FALLBACK);
+        assert_eq!(resolve_entrypoint_state(STATE_VIEW, with_fallback), STATE_FALLBACK);
+        assert_eq!(resolve_entrypoint_state(STATE_RECEIVE, required), STATE_MAIN);
+        // contracts without recorded entrypoints are executed by `main`
+        assert_eq!(resolve_entrypoint_state(STATE_RECEIVE, 0), STATE_MAIN);
+        assert_eq!(resolve_entrypoint_state(STATE_MAIN, with_fallback), STATE_MAIN);
+    }
+}
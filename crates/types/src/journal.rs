@@ -1,7 +1,18 @@
 use crate::ExitCode;
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
 use alloy_primitives::{Address, Bytes, B256};
 
+/// Number of fields
+pub const JZKT_ACCOUNT_FIELDS_COUNT: u32 = 6;
+pub const JZKT_STORAGE_FIELDS_COUNT: u32 = 1;
+
+pub const JZKT_ACCOUNT_BALANCE_FIELD: u32 = 0;
+pub const JZKT_ACCOUNT_NONCE_FIELD: u32 = 1;
+pub const JZKT_ACCOUNT_SOURCE_CODE_SIZE_FIELD: u32 = 2;
+pub const JZKT_ACCOUNT_SOURCE_CODE_HASH_FIELD: u32 = 3;
+pub const JZKT_ACCOUNT_RWASM_CODE_SIZE_FIELD: u32 = 4;
+pub const JZKT_ACCOUNT_RWASM_CODE_HASH_FIELD: u32 = 5;
+
 #[derive(Debug, Clone)]
 pub enum JournalEvent {
     ItemChanged {
@@ -14,6 +25,14 @@ pub enum JournalEvent {
         key: [u8; 32],
         prev_state: Option<usize>,
     },
+    TransientChanged {
+        key: [u8; 32],
+        value: [u8; 32],
+        prev_state: Option<usize>,
+    },
+    KeyWarmed {
+        key: [u8; 32],
+    },
 }
 
 impl JournalEvent {
@@ -21,6 +40,8 @@ impl JournalEvent {
         match self {
             JournalEvent::ItemChanged { key, .. } => key,
             JournalEvent::ItemRemoved { key, .. } => key,
+            JournalEvent::TransientChanged { key, .. } => key,
+            JournalEvent::KeyWarmed { key } => key,
         }
     }
 
@@ -28,9 +49,24 @@ impl JournalEvent {
         match self {
             JournalEvent::ItemChanged { .. } => false,
             JournalEvent::ItemRemoved { .. } => true,
+            JournalEvent::TransientChanged { .. } => false,
+            JournalEvent::KeyWarmed { .. } => false,
         }
     }
 
+    /// Transient (EIP-1153) changes are never written into the trie
+    pub fn is_transient(&self) -> bool {
+        matches!(self, JournalEvent::TransientChanged { .. })
+    }
+
+    /// Returns true if event changes the trie state (not transient storage or access lists)
+    pub fn is_trie_change(&self) -> bool {
+        matches!(
+            self,
+            JournalEvent::ItemChanged { .. } | JournalEvent::ItemRemoved { .. }
+        )
+    }
+
     pub fn preimage(&self) -> Option<(Vec<[u8; 32]>, u32)> {
         match self {
             JournalEvent::ItemChanged {
@@ -39,6 +75,8 @@ impl JournalEvent {
                 ..
             } => Some((value.clone(), *flags)),
             JournalEvent::ItemRemoved { .. } => None,
+            JournalEvent::TransientChanged { value, .. } => Some((vec![*value], 0)),
+            JournalEvent::KeyWarmed { .. } => None,
         }
     }
 
@@ -46,6 +84,8 @@ impl JournalEvent {
         match self {
             JournalEvent::ItemChanged { prev_state, .. } => *prev_state,
             JournalEvent::ItemRemoved { prev_state, .. } => *prev_state,
+            JournalEvent::TransientChanged { prev_state, .. } => *prev_state,
+            JournalEvent::KeyWarmed { .. } => None,
         }
     }
 }
@@ -98,9 +138,14 @@ pub trait IJournaledTrie {
     fn commit(&self) -> Result<([u8; 32], Vec<JournalLog>), ExitCode>;
     fn rollback(&self, checkpoint: JournalCheckpoint);
     fn update_preimage(&self, key: &[u8; 32], field: u32, preimage: &[u8]) -> bool;
-    fn preimage(&self, hash: &[u8; 32]) -> Vec<u8>;
-    fn preimage_size(&self, hash: &[u8; 32]) -> u32;
+    /// Returns preimage of the hash stored for the account field (same as in `update_preimage`)
+    fn preimage(&self, field: u32, hash: &[u8; 32]) -> Vec<u8>;
+    fn preimage_size(&self, field: u32, hash: &[u8; 32]) -> u32;
     fn journal(&self) -> Vec<JournalEvent>;
+    /// Marks key as accessed in the current transaction, returns true if key was cold (EIP-2929)
+    fn access(&self, key: &[u8; 32]) -> bool;
+    fn transient_get(&self, key: &[u8; 32]) -> Option<[u8; 32]>;
+    fn transient_update(&self, key: &[u8; 32], value: &[u8; 32]);
 }
 
 #[derive(Default, Clone)]
@@ -144,15 +189,27 @@ impl IJournaledTrie for EmptyJournalTrie {
         todo!()
     }
 
-    fn preimage(&self, hash: &[u8; 32]) -> Vec<u8> {
+    fn preimage(&self, field: u32, hash: &[u8; 32]) -> Vec<u8> {
         todo!()
     }
 
-    fn preimage_size(&self, hash: &[u8; 32]) -> u32 {
+    fn preimage_size(&self, field: u32, hash: &[u8; 32]) -> u32 {
         todo!()
     }
 
     fn journal(&self) -> Vec<JournalEvent> {
         todo!()
     }
+
+    fn access(&self, key: &[u8; 32]) -> bool {
+        todo!()
+    }
+
+    fn transient_get(&self, key: &[u8; 32]) -> Option<[u8; 32]> {
+        todo!()
+    }
+
+    fn transient_update(&self, key: &[u8; 32], value: &[u8; 32]) {
+        todo!()
+    }
 }
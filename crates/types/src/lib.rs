@@ -29,5 +29,40 @@ pub const NATIVE_TRANSFER_KECCAK: B256 =
     b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
 pub const NATIVE_TRANSFER_ADDRESS: Address = address!("0000000000000000000000000000000000000000");
 
+/// System address (EIP-2935) that keeps history of the block hashes in its storage, where slot is
+/// a block number modulo [`BLOCK_HASH_HISTORY_SERVE_WINDOW`]
+pub const BLOCK_HASH_HISTORY_ADDRESS: Address =
+    address!("0000f90827f1c53a10cb7a02335b175320002935");
+pub const BLOCK_HASH_HISTORY_SERVE_WINDOW: u64 = 8191;
+/// Number of the last blocks available for the BLOCKHASH opcode
+pub const BLOCK_HASH_HISTORY: u64 = 256;
+
+/// Standard precompile addresses (up to Cancun), they are always warm and dispatched by address
+/// without loading the account. Precompiles that aren't deployed yet fail instead of acting like
+/// an empty account.
+pub const PRECOMPILE_ADDRESSES: [Address; 10] = [
+    address!("0000000000000000000000000000000000000001"),
+    address!("0000000000000000000000000000000000000002"),
+    address!("0000000000000000000000000000000000000003"),
+    address!("0000000000000000000000000000000000000004"),
+    address!("0000000000000000000000000000000000000005"),
+    address!("0000000000000000000000000000000000000006"),
+    address!("0000000000000000000000000000000000000007"),
+    address!("0000000000000000000000000000000000000008"),
+    address!("0000000000000000000000000000000000000009"),
+    address!("000000000000000000000000000000000000000a"),
+];
+
 pub const STATE_MAIN: u32 = 0;
 pub const STATE_DEPLOY: u32 = 1;
+/// Read-only entrypoint, it's executed in static mode without journal writes
+pub const STATE_VIEW: u32 = 2;
+/// Entrypoint for plain value transfers with empty input
+pub const STATE_RECEIVE: u32 = 3;
+/// Entrypoint for calls that don't match any other entrypoint
+pub const STATE_FALLBACK: u32 = 4;
+/// Entrypoint that is executed after contract bytecode upgrade
+pub const STATE_UPGRADE: u32 = 5;
+/// Flag that can be combined with the state passed into `_sys_exec_hash` to execute the callee
+/// in static mode, where all state-changing host functions are forbidden
+pub const STATE_STATIC_FLAG: u32 = 0x8000_0000;
@@ -45,7 +45,7 @@ pub fn create_shared_import_linker<F: From<[(&'static str, &'static str, u32, u3
     F::from(SHARED_IMPORT_LINKER)
 }
 
-const SOVEREIGN_IMPORT_LINKER: [(&'static str, &'static str, u32, u32); 29] = [
+const SOVEREIGN_IMPORT_LINKER: [(&'static str, &'static str, u32, u32); 31] = [
     import_func!("_crypto_keccak256", CRYPTO_KECCAK256),
     import_func!("_crypto_poseidon", CRYPTO_KECCAK256),
     import_func!("_crypto_poseidon2", CRYPTO_POSEIDON2),
@@ -72,12 +72,59 @@ const SOVEREIGN_IMPORT_LINKER: [(&'static str, &'static str, u32, u32); 29] = [
     import_func!("_jzkt_rollback", JZKT_ROLLBACK),
     import_func!("_jzkt_preimage_size", JZKT_PREIMAGE_SIZE),
     import_func!("_jzkt_preimage_copy", JZKT_PREIMAGE_COPY),
+    import_func!("_jzkt_transient_get", JZKT_TRANSIENT_GET),
+    import_func!("_jzkt_transient_update", JZKT_TRANSIENT_UPDATE),
     import_func!("_wasm_to_rwasm_size", WASM_TO_RWASM_SIZE),
     import_func!("_wasm_to_rwasm", WASM_TO_RWASM),
     import_func!("_debug_log", DEBUG_LOG),
 ];
 
-pub fn create_sovereign_import_linker<F: From<[(&'static str, &'static str, u32, u32); 29]>>() -> F
+pub fn create_sovereign_import_linker<F: From<[(&'static str, &'static str, u32, u32); 31]>>() -> F
 {
     F::from(SOVEREIGN_IMPORT_LINKER)
 }
+
+/// Host functions that can be imported by user contracts
+pub const SOVEREIGN_IMPORTS: &[(&str, &str, u32, u32)] = &SOVEREIGN_IMPORT_LINKER;
+
+/// Import linker of the genesis system contracts, it extends the sovereign linker with host
+/// functions that are too expensive to be exposed to user contracts (KZG pairing check)
+const SYSTEM_IMPORT_LINKER: [(&'static str, &'static str, u32, u32); 32] = [
+    import_func!("_crypto_keccak256", CRYPTO_KECCAK256),
+    import_func!("_crypto_poseidon", CRYPTO_KECCAK256),
+    import_func!("_crypto_poseidon2", CRYPTO_POSEIDON2),
+    import_func!("_crypto_ecrecover", CRYPTO_ECRECOVER),
+    import_func!("_crypto_kzg_point_evaluation", CRYPTO_KZG_POINT_EVALUATION),
+    import_func!("_sys_halt", SYS_HALT),
+    import_func!("_sys_write", SYS_WRITE),
+    import_func!("_sys_input_size", SYS_INPUT_SIZE),
+    import_func!("_sys_read", SYS_READ),
+    import_func!("_sys_output_size", SYS_OUTPUT_SIZE),
+    import_func!("_sys_read_output", SYS_READ_OUTPUT),
+    import_func!("_sys_forward_output", SYS_FORWARD_OUTPUT),
+    import_func!("_sys_state", SYS_STATE),
+    import_func!("_sys_exec_hash", SYS_EXEC_HASH),
+    import_func!("_sys_fuel", SYS_FUEL),
+    import_func!("_jzkt_open", JZKT_OPEN),
+    import_func!("_jzkt_checkpoint", JZKT_CHECKPOINT),
+    import_func!("_jzkt_get", JZKT_GET),
+    import_func!("_jzkt_update", JZKT_UPDATE),
+    import_func!("_jzkt_update_preimage", JZKT_UPDATE_PREIMAGE),
+    import_func!("_jzkt_remove", JZKT_REMOVE),
+    import_func!("_jzkt_compute_root", JZKT_COMPUTE_ROOT),
+    import_func!("_jzkt_emit_log", JZKT_EMIT_LOG),
+    import_func!("_jzkt_commit", JZKT_COMMIT),
+    import_func!("_jzkt_rollback", JZKT_ROLLBACK),
+    import_func!("_jzkt_preimage_size", JZKT_PREIMAGE_SIZE),
+    import_func!("_jzkt_preimage_copy", JZKT_PREIMAGE_COPY),
+    import_func!("_jzkt_transient_get", JZKT_TRANSIENT_GET),
+    import_func!("_jzkt_transient_update", JZKT_TRANSIENT_UPDATE),
+    import_func!("_wasm_to_rwasm_size", WASM_TO_RWASM_SIZE),
+    import_func!("_wasm_to_rwasm", WASM_TO_RWASM),
+    import_func!("_debug_log", DEBUG_LOG),
+];
+
+pub fn create_system_import_linker<F: From<[(&'static str, &'static str, u32, u32); 32]>>() -> F
+{
+    F::from(SYSTEM_IMPORT_LINKER)
+}
@@ -50,6 +50,13 @@ pub enum ExitCode {
     InvalidEfOpcode = -1031,
     InvalidJump = -1032,
     NotActivatedEIP = -1033,
+    MalformedWasmModule = -1034,
+    FloatInstructionsNotAllowed = -1035,
+    UnsupportedWasmFeature = -1036,
+    UnknownImport = -1037,
+    MissingEntrypoint = -1038,
+    WasmLimitExceeded = -1039,
+    InvalidEofContainer = -1040,
     // trap error codes
     UnreachableCodeReached = -2006,
     MemoryOutOfBounds = -2007,
@@ -142,6 +149,7 @@ pub enum SysFuncIdx {
     CRYPTO_POSEIDON = 0x0102,
     CRYPTO_POSEIDON2 = 0x0103,
     CRYPTO_ECRECOVER = 0x0104,
+    CRYPTO_KZG_POINT_EVALUATION = 0x0105,
 
     // SYS host
     SYS_HALT = 0x0001,
@@ -168,6 +176,8 @@ pub enum SysFuncIdx {
     JZKT_ROLLBACK = 0x070A,
     JZKT_PREIMAGE_SIZE = 0x070D,
     JZKT_PREIMAGE_COPY = 0x070E,
+    JZKT_TRANSIENT_GET = 0x070F,
+    JZKT_TRANSIENT_UPDATE = 0x0710,
 
     // rwasm
     WASM_TO_RWASM_SIZE = 0x0801,
@@ -209,6 +219,7 @@ impl From<u32> for SysFuncIdx {
             0x0102 => Self::CRYPTO_POSEIDON,
             0x0103 => Self::CRYPTO_POSEIDON2,
             0x0104 => Self::CRYPTO_ECRECOVER,
+            0x0105 => Self::CRYPTO_KZG_POINT_EVALUATION,
 
             // SYS host
             0x0001 => Self::SYS_HALT,
@@ -234,6 +245,8 @@ impl From<u32> for SysFuncIdx {
             0x070A => Self::JZKT_ROLLBACK,
             0x070D => Self::JZKT_PREIMAGE_SIZE,
             0x070E => Self::JZKT_PREIMAGE_COPY,
+            0x070F => Self::JZKT_TRANSIENT_GET,
+            0x0710 => Self::JZKT_TRANSIENT_UPDATE,
 
             0x0801 => Self::WASM_TO_RWASM_SIZE,
             0x0802 => Self::WASM_TO_RWASM,
@@ -1,5 +1,6 @@
 use fluentbase_codec::Encoder;
 use fluentbase_core::{Account, JZKT_ACCOUNT_COMPRESSION_FLAGS};
+use fluentbase_genesis::{chain_config_hardforks, devnet::devnet_chain_config};
 use fluentbase_runtime::{DefaultEmptyRuntimeDatabase, ExecutionResult, Runtime, RuntimeContext};
 use fluentbase_sdk::{ContractInput, LowLevelSDK};
 use fluentbase_types::{Address, Bytes, IJournaledTrie, STATE_DEPLOY, STATE_MAIN, U256};
@@ -117,9 +118,18 @@ macro_rules! impl_once_setter {
     };
 }
 
-#[derive(Default)]
 pub(crate) struct ContractInputWrapper(ContractInput);
 
+impl Default for ContractInputWrapper {
+    fn default() -> Self {
+        // standalone context follows the hardfork schedule of the devnet chain
+        Self(ContractInput {
+            block_hardforks: chain_config_hardforks(&devnet_chain_config()),
+            ..Default::default()
+        })
+    }
+}
+
 #[allow(dead_code)]
 impl ContractInputWrapper {
     impl_once_setter!(journal_checkpoint, u64);
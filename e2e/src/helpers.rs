@@ -4,10 +4,10 @@ use fluentbase_runtime::{
     DefaultEmptyRuntimeDatabase, ExecutionResult, Runtime, RuntimeContext,
 };
 use fluentbase_sdk::ContractInput;
-use fluentbase_types::SysFuncIdx::SYS_STATE;
-use fluentbase_types::{create_sovereign_import_linker, Bytes, ExitCode, STATE_DEPLOY, STATE_MAIN};
-use rwasm::engine::bytecode::Instruction;
-use rwasm::engine::{RwasmConfig, StateRouterConfig};
+use fluentbase_types::{
+    create_sovereign_import_linker, create_state_router_config, Bytes, ExitCode, STATE_MAIN,
+};
+use rwasm::engine::RwasmConfig;
 use rwasm::rwasm::{BinaryFormat, BinaryFormatWriter, RwasmModule};
 use rwasm::{Config, Engine, Error, Linker, Module, Store};
 
@@ -42,13 +42,7 @@ pub(crate) fn catch_panic(ctx: &ExecutionResult) {
 pub fn rwasm_module(wasm_binary: &[u8]) -> Result<RwasmModule, Error> {
     let mut config = RwasmModule::default_config(None);
     config.rwasm_config(RwasmConfig {
-        state_router: Some(StateRouterConfig {
-            states: Box::new([
-                ("deploy".to_string(), STATE_DEPLOY),
-                ("main".to_string(), STATE_MAIN),
-            ]),
-            opcode: Instruction::Call(SYS_STATE.into()),
-        }),
+        state_router: Some(create_state_router_config()),
         entrypoint_name: None,
         import_linker: Some(create_sovereign_import_linker()),
         wrap_import_functions: true,
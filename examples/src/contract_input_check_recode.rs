@@ -20,6 +20,7 @@ pub fn main() {
         block_difficulty: ctx.block_difficulty(),
         block_gas_limit: ctx.block_gas_limit(),
         block_base_fee: ctx.block_base_fee(),
+        block_hardforks: ctx.block_hardforks(),
         tx_gas_limit: ctx.tx_gas_limit(),
         tx_nonce: ctx.tx_nonce(),
         tx_gas_price: ctx.tx_gas_price(),